@@ -3,13 +3,20 @@ use std::io;
 use anyhow::anyhow;
 use futures::{AsyncBufReadExt, Stream, StreamExt, TryStreamExt};
 use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
 
 use crate::openai::completions::{
-    OpenAIChatCompletionRequest, OpenAIChatCompletionRequestReasoning, OpenAICompletionChunk,
-    OpenAIMessage, OpenAIPromptCompletionRequest, OpenAIPromptCompletionResponse, ReasoningEffort,
+    CompletionParameters, OpenAIChatCompletionRequest, OpenAIChatCompletionRequestReasoning,
+    OpenAIChatCompletionResponse, OpenAICompletionChunk, OpenAIMessage,
+    OpenAIPromptCompletionRequest, OpenAIPromptCompletionResponse, ResponseFormat,
 };
 
-use super::completions::OpenRouterRequestPlugin;
+use super::{
+    completions::OpenRouterRequestPlugin,
+    embeddings::{OpenAIEmbeddingRequest, OpenAIEmbeddingResponse},
+    models::{OpenAIModel, OpenAIModelList},
+    structured::{self, StructuredRequest},
+};
 
 #[derive(Debug, Clone)]
 pub struct OpenAIClient {
@@ -26,8 +33,7 @@ impl OpenAIClient {
         self,
         model: String,
         messages: Vec<OpenAIMessage>,
-        temperature: Option<f32>,
-        reasoning_effort: Option<ReasoningEffort>,
+        parameters: CompletionParameters,
         plugins: Vec<OpenRouterRequestPlugin>,
     ) -> anyhow::Result<impl Stream<Item = anyhow::Result<OpenAICompletionChunk>>> {
         let client = Client::new();
@@ -36,11 +42,16 @@ impl OpenAIClient {
             model,
             messages,
             stream: true,
-            temperature,
-            max_tokens: None,
-            reasoning: reasoning_effort
+            temperature: parameters.temperature,
+            top_p: parameters.top_p,
+            max_tokens: parameters.max_tokens,
+            stop: parameters.stop,
+            seed: parameters.seed,
+            reasoning: parameters
+                .reasoning_effort
                 .map(|effort| OpenAIChatCompletionRequestReasoning { effort }),
             plugins,
+            response_format: None,
         };
 
         let request = client
@@ -57,15 +68,14 @@ impl OpenAIClient {
         let bytes_stream = request.bytes_stream();
 
         Ok(bytes_stream
-            .map_err(|e| io::Error::other(e))
+            .map_err(io::Error::other)
             .into_async_read()
             .lines()
             .filter_map(|line| async move {
                 match line {
                     Ok(line) => {
                         // OpenAI sends lines like "data: {json}" or "data: [DONE]"
-                        if line.starts_with("data: ") {
-                            let json_str = &line[6..]; // Remove "data: " prefix
+                        if let Some(json_str) = line.strip_prefix("data: ") {
                             if json_str == "[DONE]" {
                                 return None; // Signal to terminate the stream
                             }
@@ -119,4 +129,181 @@ impl OpenAIClient {
 
         Ok(response.choices.remove(0).text.trim().to_string())
     }
+
+    pub async fn chat_completion_non_streaming(
+        self,
+        model: String,
+        messages: Vec<OpenAIMessage>,
+        parameters: CompletionParameters,
+        response_format: Option<ResponseFormat>,
+    ) -> anyhow::Result<String> {
+        let client = Client::new();
+
+        let openai_req_body = OpenAIChatCompletionRequest {
+            model,
+            messages,
+            stream: false,
+            temperature: parameters.temperature,
+            top_p: parameters.top_p,
+            max_tokens: parameters.max_tokens,
+            stop: parameters.stop,
+            seed: parameters.seed,
+            reasoning: parameters
+                .reasoning_effort
+                .map(|effort| OpenAIChatCompletionRequestReasoning { effort }),
+            plugins: vec![],
+            response_format,
+        };
+
+        let response = client
+            .post(format!("{}/v1/chat/completions", self.base_url))
+            .bearer_auth(self.key)
+            .json(&openai_req_body)
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            anyhow::bail!(response.status())
+        }
+
+        let mut response: OpenAIChatCompletionResponse = response.json().await?;
+        if response.choices.is_empty() {
+            anyhow::bail!("Completion has no choices");
+        }
+
+        Ok(response
+            .choices
+            .remove(0)
+            .message
+            .content
+            .unwrap_or_default()
+            .trim()
+            .to_string())
+    }
+
+    /// Requests JSON output that deserializes into `T` and passes `validate`. Uses the strictest
+    /// response format the provider accepts, and asks the model to correct malformed output
+    /// until `request.max_attempts` completions were made.
+    pub async fn structured_completion<T, U>(
+        self,
+        model: String,
+        request: StructuredRequest,
+        validate: impl Fn(T) -> anyhow::Result<U>,
+    ) -> anyhow::Result<U>
+    where
+        T: DeserializeOwned,
+    {
+        let parameters = CompletionParameters {
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            ..Default::default()
+        };
+        let mut formats = request.formats().into_iter().peekable();
+        let mut messages = request.messages();
+        let mut last_error = anyhow!("No completion was requested");
+
+        let mut attempts = 0;
+        while attempts < request.max_attempts {
+            let Some(format) = formats.peek().cloned() else {
+                break;
+            };
+
+            let output = match self
+                .clone()
+                .chat_completion_non_streaming(
+                    model.clone(),
+                    messages.clone(),
+                    parameters.clone(),
+                    format.clone(),
+                )
+                .await
+            {
+                Ok(output) => output,
+                Err(e)
+                    if format.is_some()
+                        && e.downcast_ref::<StatusCode>().is_some_and(|status| {
+                            *status == StatusCode::BAD_REQUEST
+                                || *status == StatusCode::UNPROCESSABLE_ENTITY
+                        }) =>
+                {
+                    // the provider does not support this response format, so fall back to the next
+                    formats.next();
+                    last_error = e;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            attempts += 1;
+
+            match structured::parse(&output, &validate) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    messages.extend(structured::retry_messages(&output, &e));
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(last_error.context("No valid structured output"))
+    }
+
+    pub async fn models(self) -> anyhow::Result<Vec<OpenAIModel>> {
+        let client = Client::new();
+
+        let response = client
+            .get(format!("{}/v1/models", self.base_url))
+            .bearer_auth(self.key)
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            anyhow::bail!(response.status())
+        }
+
+        let list: OpenAIModelList = response.json().await?;
+
+        Ok(list.data)
+    }
+
+    /// Embeds every input, returning the vectors in input order.
+    pub async fn embeddings(
+        self,
+        model: String,
+        input: Vec<String>,
+        dimensions: Option<u32>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        let client = Client::new();
+        let expected = input.len();
+
+        let response = client
+            .post(format!("{}/v1/embeddings", self.base_url))
+            .bearer_auth(self.key)
+            .json(&OpenAIEmbeddingRequest {
+                model,
+                input,
+                dimensions,
+                encoding_format: "float",
+            })
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            anyhow::bail!(response.status())
+        }
+
+        let mut response: OpenAIEmbeddingResponse = response.json().await?;
+        if response.data.len() != expected {
+            anyhow::bail!(
+                "Expected {expected} embeddings, got {}",
+                response.data.len()
+            );
+        }
+        response.data.sort_by_key(|embedding| embedding.index);
+
+        Ok(response
+            .data
+            .into_iter()
+            .map(|embedding| embedding.embedding)
+            .collect())
+    }
 }
@@ -8,10 +8,44 @@ pub struct OpenAIChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<OpenAIChatCompletionRequestReasoning>,
     pub plugins: Vec<OpenRouterRequestPlugin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+}
+
+/// Constrains the completion to JSON. Not every provider supports both kinds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    JsonObject,
+    JsonSchema { json_schema: ResponseJsonSchema },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseJsonSchema {
+    pub name: String,
+    pub schema: serde_json::Value,
+    pub strict: bool,
+}
+
+/// Sampling and length controls for a chat completion. Unset values use the provider defaults.
+#[derive(Debug, Clone, Default)]
+pub struct CompletionParameters {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub stop: Vec<String>,
+    pub seed: Option<i64>,
+    pub reasoning_effort: Option<ReasoningEffort>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,13 +64,13 @@ pub struct OpenAIChatCompletionRequestReasoning {
     pub effort: ReasoningEffort,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenAIMessage {
     pub role: String,
     pub content: Vec<OpenAIMessageContent>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum OpenAIMessageContent {
     #[serde(rename = "text")]
@@ -47,13 +81,13 @@ pub enum OpenAIMessageContent {
     File { file: OpenAIMessageContentFile },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenAIMessageContentFile {
     pub filename: String,
     pub file_data: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenAIMessageImageUrl {
     pub url: String,
 }
@@ -85,7 +119,7 @@ pub struct OpenAICompletionDelta {
     pub role: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReasoningEffort {
     #[serde(rename = "high")]
     High,
@@ -117,3 +151,19 @@ pub struct OpenAIPromptCompletionResponse {
 pub struct OpenAIPromptCompletionChoice {
     pub text: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAIChatCompletionResponse {
+    pub id: String,
+    pub choices: Vec<OpenAIChatCompletionResponseChoice>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAIChatCompletionResponseChoice {
+    pub message: OpenAIChatCompletionResponseMessage,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAIChatCompletionResponseMessage {
+    pub content: Option<String>,
+}
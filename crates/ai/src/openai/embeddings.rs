@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize)]
+pub struct OpenAIEmbeddingRequest {
+    pub model: String,
+    pub input: Vec<String>,
+    /// Truncates vectors to this many dimensions, for models that support it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    pub encoding_format: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenAIEmbeddingResponse {
+    pub data: Vec<OpenAIEmbedding>,
+    pub usage: Option<OpenAIEmbeddingUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenAIEmbedding {
+    pub index: usize,
+    pub embedding: Vec<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenAIEmbeddingUsage {
+    pub prompt_tokens: u32,
+}
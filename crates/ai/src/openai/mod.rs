@@ -1,2 +1,5 @@
 pub mod client;
 pub mod completions;
+pub mod embeddings;
+pub mod models;
+pub mod structured;
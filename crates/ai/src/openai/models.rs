@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAIModelList {
+    pub data: Vec<OpenAIModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIModel {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<OpenAIModelPricing>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<OpenAIModelArchitecture>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_provider: Option<OpenAIModelTopProvider>,
+    #[serde(default)]
+    pub supported_parameters: Vec<String>,
+}
+
+/// Prices are per token, encoded as decimal strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIModelPricing {
+    pub prompt: String,
+    pub completion: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIModelArchitecture {
+    #[serde(default)]
+    pub input_modalities: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIModelTopProvider {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+}
@@ -0,0 +1,120 @@
+use serde::de::DeserializeOwned;
+
+use super::completions::{OpenAIMessage, OpenAIMessageContent, ResponseFormat, ResponseJsonSchema};
+
+/// Tags reasoning models wrap their chain of thought in.
+const REASONING_TAGS: [&str; 3] = ["think", "thinking", "reasoning"];
+
+/// Request for a completion that must deserialize into a known shape.
+#[derive(Debug, Clone)]
+pub struct StructuredRequest {
+    /// Name of the schema, sent to providers that support JSON schemas.
+    pub name: String,
+    /// JSON schema the output must follow.
+    pub schema: serde_json::Value,
+    pub prompt: String,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    /// Completions requested before giving up on malformed output.
+    pub max_attempts: u32,
+}
+
+impl StructuredRequest {
+    pub fn new(name: impl Into<String>, schema: serde_json::Value, prompt: String) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            prompt,
+            temperature: None,
+            max_tokens: None,
+            max_attempts: 3,
+        }
+    }
+
+    /// Response formats to try, strictest first. Later ones are used when a provider
+    /// rejects the earlier ones.
+    pub(crate) fn formats(&self) -> [Option<ResponseFormat>; 3] {
+        [
+            Some(ResponseFormat::JsonSchema {
+                json_schema: ResponseJsonSchema {
+                    name: self.name.clone(),
+                    schema: self.schema.clone(),
+                    strict: true,
+                },
+            }),
+            Some(ResponseFormat::JsonObject),
+            None,
+        ]
+    }
+
+    pub(crate) fn messages(&self) -> Vec<OpenAIMessage> {
+        vec![text_message(
+            "user",
+            format!(
+                "{}\n\nRespond with a single JSON value matching this schema and nothing else:\n{}",
+                self.prompt, self.schema
+            ),
+        )]
+    }
+}
+
+/// Turns a malformed output into a follow-up asking the model to correct it.
+pub(crate) fn retry_messages(output: &str, error: &anyhow::Error) -> [OpenAIMessage; 2] {
+    [
+        text_message("assistant", output.to_string()),
+        text_message(
+            "user",
+            format!(
+                "Your output was invalid: {error}. Respond again with only the corrected JSON."
+            ),
+        ),
+    ]
+}
+
+fn text_message(role: &str, text: String) -> OpenAIMessage {
+    OpenAIMessage {
+        role: role.to_string(),
+        content: vec![OpenAIMessageContent::Text { text }],
+    }
+}
+
+/// Removes reasoning blocks such as `<think>...</think>`. Output whose reasoning was cut off
+/// before the closing tag is empty, as it holds no answer.
+pub fn strip_reasoning(text: &str) -> &str {
+    let mut text = text;
+    for tag in REASONING_TAGS {
+        let close = format!("</{tag}>");
+        if let Some((_, answer)) = text.rsplit_once(&close) {
+            // some providers omit the opening tag, so everything before the last close is dropped
+            text = answer;
+        } else if text.trim_start().starts_with(&format!("<{tag}>")) {
+            return "";
+        }
+    }
+    text.trim()
+}
+
+/// Finds the JSON value in a model's answer, ignoring code fences and surrounding prose.
+pub fn extract_json(text: &str) -> Option<&str> {
+    let start = text.find(['{', '['])?;
+    let close = if text[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = text.rfind(close)?;
+    (end > start).then(|| &text[start..=end])
+}
+
+/// Parses and validates a completion, returning the reason it is unusable otherwise.
+pub(crate) fn parse<T, U>(
+    output: &str,
+    validate: &impl Fn(T) -> anyhow::Result<U>,
+) -> anyhow::Result<U>
+where
+    T: DeserializeOwned,
+{
+    let answer = strip_reasoning(output);
+    let json = extract_json(answer).ok_or_else(|| anyhow::anyhow!("no JSON value found"))?;
+    validate(serde_json::from_str(json)?)
+}
@@ -0,0 +1,222 @@
+use anyhow::anyhow;
+use futures::TryStreamExt;
+use model::{
+    assistant::Assistant,
+    chat::{Chat, ChatSettings},
+    share::Share,
+    upload::UserUpload,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use redis_om::HashModel;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    state::AppState,
+};
+
+/// Loads an assistant the user owns or that its owner currently shares by link.
+/// `share_id` must match the active share link when the assistant belongs to someone else.
+pub async fn load_accessible(
+    state: &AppState,
+    assistant_id: ObjectId,
+    user_id: ObjectId,
+    share_id: Option<ObjectId>,
+) -> Result<Assistant, ApplicationError> {
+    let assistant = load(state, assistant_id).await?;
+    if assistant.user_id == user_id {
+        return Ok(assistant);
+    }
+
+    let Some(share) = active_share(state, assistant_id).await else {
+        return Err(ApplicationError::AssistantDoesNotBelongToUser);
+    };
+    match share_id {
+        Some(share_id) if share_id.to_hex() == share.share_id => Ok(assistant),
+        _ => Err(ApplicationError::InvalidShareLink),
+    }
+}
+
+/// Assistant a chat was created from, if its owner still lets the user access it.
+/// Deleted or unshared assistants leave the chat as a plain chat instead of breaking it.
+/// Someone else's assistant stays accessible while the share link the chat was created with is
+/// active.
+pub async fn for_chat(
+    state: &AppState,
+    chat: &Chat,
+    user_id: ObjectId,
+) -> Result<Option<Assistant>, ApplicationError> {
+    let Some(assistant_id) = chat.assistant_id else {
+        return Ok(None);
+    };
+
+    let assistant = match load(state, assistant_id).await {
+        Ok(assistant) => assistant,
+        Err(ApplicationError::AssistantDoesNotExist) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if assistant.user_id == user_id {
+        return Ok(Some(assistant));
+    }
+
+    let shared = match (chat.share_id, active_share(state, assistant_id).await) {
+        (Some(share_id), Some(share)) => share_id.to_hex() == share.share_id,
+        _ => false,
+    };
+    Ok(shared.then_some(assistant))
+}
+
+async fn load(state: &AppState, assistant_id: ObjectId) -> Result<Assistant, ApplicationError> {
+    state
+        .storage()
+        .database()
+        .assistants
+        .get_by_id(assistant_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .ok_or(ApplicationError::AssistantDoesNotExist)
+}
+
+async fn active_share(state: &AppState, assistant_id: ObjectId) -> Option<Share> {
+    let mut conn = state.storage().cache().connection();
+    Share::get(assistant_id.to_hex(), &mut conn).await.ok()
+}
+
+/// Settings a chat runs with: anything the chat sets overrides the assistant's defaults.
+/// Both system prompts are kept, the assistant's first so the chat can refine the persona.
+pub fn effective_settings(assistant: Option<&Assistant>, chat: &ChatSettings) -> ChatSettings {
+    let Some(assistant) = assistant else {
+        return chat.clone();
+    };
+    let defaults = &assistant.settings;
+
+    let system_prompt = match (&defaults.system_prompt, &chat.system_prompt) {
+        (Some(persona), Some(prompt)) => Some(format!("{persona}\n\n{prompt}")),
+        (persona, prompt) => prompt.clone().or_else(|| persona.clone()),
+    };
+
+    ChatSettings {
+        system_prompt,
+        temperature: chat.temperature.or(defaults.temperature),
+        top_p: chat.top_p.or(defaults.top_p),
+        max_tokens: chat.max_tokens.or(defaults.max_tokens),
+        stop: if chat.stop.is_empty() {
+            defaults.stop.clone()
+        } else {
+            chat.stop.clone()
+        },
+        seed: chat.seed.or(defaults.seed),
+        default_model: chat
+            .default_model
+            .clone()
+            .or_else(|| defaults.default_model.clone()),
+        default_reasoning_effort: chat
+            .default_reasoning_effort
+            .or(defaults.default_reasoning_effort),
+    }
+}
+
+/// Checks that every knowledge file is an upload of the user that no other assistant pins.
+pub async fn validate_knowledge(
+    state: &AppState,
+    user_id: ObjectId,
+    assistant_id: Option<ObjectId>,
+    knowledge: &[ObjectId],
+) -> Result<(), ApplicationError> {
+    let uploads = state
+        .storage()
+        .database()
+        .uploads
+        .get_many(doc! { "_id": { "$in": knowledge }, "user_id": user_id })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .try_collect::<Vec<UserUpload>>()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow!(e),
+            )))
+        })?;
+
+    if uploads.len() != knowledge.len() {
+        return Err(ApplicationError::UploadNotFound);
+    }
+    if uploads
+        .iter()
+        .any(|upload| upload.assistant_id.is_some() && upload.assistant_id != assistant_id)
+    {
+        return Err(ApplicationError::UploadPinnedToAnotherAssistant);
+    }
+
+    Ok(())
+}
+
+/// Pins uploads to the assistant and releases the ones no longer in `knowledge`.
+/// Pinned uploads are marked as sent so they are not picked up as attachments of a message.
+pub async fn pin_knowledge(
+    state: &AppState,
+    assistant_id: ObjectId,
+    previous: &[ObjectId],
+    knowledge: &[ObjectId],
+) -> Result<(), ApplicationError> {
+    for id in previous.iter().filter(|id| !knowledge.contains(id)) {
+        state
+            .storage()
+            .database()
+            .uploads
+            .update(*id, doc! { "$set": { "assistant_id": null } })
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+    }
+    for id in knowledge.iter() {
+        state
+            .storage()
+            .database()
+            .uploads
+            .update(
+                *id,
+                doc! { "$set": { "assistant_id": assistant_id, "is_sent": true } },
+            )
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Knowledge files attached to every message sent to the assistant.
+pub async fn knowledge(
+    state: &AppState,
+    assistant: &Assistant,
+) -> Result<Vec<UserUpload>, ApplicationError> {
+    state
+        .storage()
+        .database()
+        .uploads
+        .get_many(doc! { "_id": { "$in": &assistant.knowledge }, "assistant_id": assistant.id })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .try_collect::<Vec<UserUpload>>()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow!(e),
+            )))
+        })
+}
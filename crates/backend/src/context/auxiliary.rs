@@ -0,0 +1,69 @@
+use ai::openai::client::OpenAIClient;
+use mongodb::bson::oid::ObjectId;
+
+use crate::{context::keys, state::AppState};
+
+#[derive(Debug, Clone, Copy)]
+pub enum AuxiliaryTask {
+    Titles,
+    Memories,
+    Summaries,
+}
+
+/// Client and model an auxiliary task runs with.
+#[derive(Debug, Clone)]
+pub struct AuxiliaryModel {
+    pub client: OpenAIClient,
+    pub model: String,
+}
+
+pub fn is_enabled(state: &AppState, task: AuxiliaryTask) -> bool {
+    let config = &state.inference().auxiliary;
+    match task {
+        AuxiliaryTask::Titles => config.titles,
+        AuxiliaryTask::Memories => config.memories,
+        AuxiliaryTask::Summaries => config.summaries,
+    }
+}
+
+/// Picks the model for a task: the configured auxiliary model if its provider is available,
+/// otherwise the chat's model through the user's own key or the server's provider key.
+/// Returns `None` when the task is disabled or no model can run it.
+pub async fn resolve(
+    state: &AppState,
+    task: AuxiliaryTask,
+    user_id: ObjectId,
+    chat_model: &str,
+) -> anyhow::Result<Option<AuxiliaryModel>> {
+    if !is_enabled(state, task) {
+        return Ok(None);
+    }
+
+    let config = &state.inference().auxiliary;
+    let configured = config
+        .model
+        .clone()
+        .zip(state.inference().client(config.provider));
+    if let Some((model, client)) = configured {
+        return Ok(Some(AuxiliaryModel { client, model }));
+    }
+
+    if chat_model.is_empty() {
+        return Ok(None);
+    }
+    if let Some(key) = keys::openrouter_key(state, user_id).await? {
+        return Ok(Some(AuxiliaryModel {
+            client: OpenAIClient::new(key, "https://openrouter.ai/api".to_string()),
+            model: chat_model.to_string(),
+        }));
+    }
+
+    Ok(state
+        .models()
+        .find(chat_model)
+        .and_then(|model| state.inference().client(model.provider))
+        .map(|client| AuxiliaryModel {
+            client,
+            model: chat_model.to_string(),
+        }))
+}
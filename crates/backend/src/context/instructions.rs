@@ -0,0 +1,39 @@
+use ai::openai::completions::{OpenAIMessage, OpenAIMessageContent};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use model::{message::Role, user::UserSettings};
+
+/// System message carrying the user's custom instructions and the current date in their time zone.
+pub fn user_context_message(settings: &UserSettings, now: DateTime<Utc>) -> OpenAIMessage {
+    let timezone: Tz = settings
+        .timezone
+        .as_deref()
+        .and_then(|timezone| timezone.parse().ok())
+        .unwrap_or(Tz::UTC);
+    let mut text = format!(
+        "The current date and time is {} ({}).",
+        now.with_timezone(&timezone).format("%A, %B %-d, %Y %H:%M"),
+        timezone.name()
+    );
+
+    if let Some(ref locale) = settings.locale {
+        text.push_str(&format!(
+            "\nThe user's locale is {locale}; answer in its language unless asked otherwise."
+        ));
+    }
+    if let Some(ref about_me) = settings.about_me {
+        text.push_str(&format!(
+            "\n\nWhat the user shared about themselves:\n{about_me}"
+        ));
+    }
+    if let Some(ref instructions) = settings.response_instructions {
+        text.push_str(&format!(
+            "\n\nHow the user would like you to respond:\n{instructions}"
+        ));
+    }
+
+    OpenAIMessage {
+        role: Role::System.to_string(),
+        content: vec![OpenAIMessageContent::Text { text }],
+    }
+}
@@ -0,0 +1,35 @@
+use model::key::UserApiKey;
+use mongodb::bson::{doc, oid::ObjectId};
+use redis_om::HashModel;
+
+use crate::state::AppState;
+
+/// The user's own OpenRouter API key, decrypted, if they enrolled one.
+pub async fn openrouter_key(state: &AppState, user_id: ObjectId) -> anyhow::Result<Option<String>> {
+    let mut conn = state.storage().cache().connection();
+
+    let key =
+        if let Ok(cached_key) = UserApiKey::get(format!("openrouter-{user_id}"), &mut conn).await {
+            Some(cached_key.key)
+        } else {
+            let key = state
+                .storage()
+                .database()
+                .keys
+                .get(doc! { "user_id": user_id })
+                .await?;
+            if let Some(ref key) = key {
+                let _ = UserApiKey {
+                    id: format!("openrouter-{user_id}"),
+                    key: key.key.clone(),
+                    key_id: key.id.unwrap().to_hex(),
+                }
+                .save(&mut conn)
+                .await;
+            }
+
+            key.map(|key| key.key)
+        };
+
+    key.map(|key| state.crypto().decrypt_key(&key)).transpose()
+}
@@ -0,0 +1,10 @@
+pub mod assistant;
+pub mod auxiliary;
+pub mod instructions;
+pub mod keys;
+pub mod parameters;
+pub mod project;
+pub mod summary;
+pub mod title;
+pub mod tokens;
+pub mod window;
@@ -0,0 +1,39 @@
+use ai::openai::completions::{CompletionParameters, ReasoningEffort};
+use model::chat::{self, ChatSettings};
+
+use crate::models::Model;
+
+/// Used when neither the chat nor the request set a temperature.
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+
+pub fn reasoning_effort(effort: chat::ReasoningEffort) -> ReasoningEffort {
+    match effort {
+        chat::ReasoningEffort::Low => ReasoningEffort::Low,
+        chat::ReasoningEffort::Medium => ReasoningEffort::Medium,
+        chat::ReasoningEffort::High => ReasoningEffort::High,
+    }
+}
+
+/// Combines the chat's generation settings with the request and the model's limits.
+/// Reasoning effort is dropped for models that would reject or ignore it.
+pub fn completion_parameters(
+    settings: &ChatSettings,
+    model: &Model,
+    reasoning: Option<ReasoningEffort>,
+) -> CompletionParameters {
+    let max_tokens = match (settings.max_tokens, model.capabilities.max_output) {
+        (Some(requested), Some(limit)) => Some(requested.min(limit)),
+        (requested, _) => requested,
+    };
+
+    CompletionParameters {
+        temperature: Some(settings.temperature.unwrap_or(DEFAULT_TEMPERATURE)),
+        top_p: settings.top_p,
+        max_tokens,
+        stop: settings.stop.clone(),
+        seed: settings.seed,
+        reasoning_effort: reasoning
+            .or(settings.default_reasoning_effort.map(reasoning_effort))
+            .filter(|_| model.accepts_reasoning_effort()),
+    }
+}
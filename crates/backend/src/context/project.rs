@@ -0,0 +1,100 @@
+use ai::openai::completions::{OpenAIMessage, OpenAIMessageContent};
+use anyhow::anyhow;
+use futures::TryStreamExt;
+use model::{chat::Chat, message::Role, project::Project, upload::UserUpload};
+use mongodb::bson::{Document, doc, oid::ObjectId};
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    state::AppState,
+};
+
+pub async fn load_owned(
+    state: &AppState,
+    project_id: ObjectId,
+    user_id: ObjectId,
+) -> Result<Project, ApplicationError> {
+    let project = state
+        .storage()
+        .database()
+        .projects
+        .get_by_id(project_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .ok_or(ApplicationError::ProjectDoesNotExist)?;
+
+    if project.user_id != user_id {
+        return Err(ApplicationError::ProjectDoesNotBelongToUser);
+    }
+
+    Ok(project)
+}
+
+/// Project the chat belongs to. A deleted project leaves the chat as a plain chat.
+pub async fn for_chat(state: &AppState, chat: &Chat) -> Result<Option<Project>, ApplicationError> {
+    let Some(project_id) = chat.project_id else {
+        return Ok(None);
+    };
+
+    match load_owned(state, project_id, chat.user_id).await {
+        Ok(project) => Ok(Some(project)),
+        Err(ApplicationError::ProjectDoesNotExist) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// System message carrying the project's instructions, if it has any.
+pub fn context_message(project: &Project) -> Option<OpenAIMessage> {
+    let instructions = project.instructions.as_ref()?;
+
+    Some(OpenAIMessage {
+        role: Role::System.to_string(),
+        content: vec![OpenAIMessageContent::Text {
+            text: format!(
+                "This conversation is part of the project \"{}\". Project instructions:\n{instructions}",
+                project.name
+            ),
+        }],
+    })
+}
+
+/// Files available to every chat in the project.
+pub async fn files(
+    state: &AppState,
+    project_id: ObjectId,
+) -> Result<Vec<UserUpload>, ApplicationError> {
+    state
+        .storage()
+        .database()
+        .uploads
+        .get_many(doc! { "project_id": project_id })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .try_collect::<Vec<UserUpload>>()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow!(e),
+            )))
+        })
+}
+
+/// Memories visible in a chat: those of its project when the project scopes memories,
+/// otherwise the ones that are not scoped to any project.
+pub fn memory_filter(user_id: ObjectId, project: Option<&Project>) -> Document {
+    doc! { "user_id": user_id, "project_id": memory_project_id(project) }
+}
+
+/// Project new memories from a chat are scoped to.
+pub fn memory_project_id(project: Option<&Project>) -> Option<ObjectId> {
+    project
+        .filter(|project| project.scoped_memories)
+        .and_then(|project| project.id)
+}
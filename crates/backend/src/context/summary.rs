@@ -0,0 +1,150 @@
+use ai::openai::{
+    completions::{CompletionParameters, OpenAIMessage, OpenAIMessageContent},
+    structured,
+};
+use chrono::Utc;
+use model::{
+    chat::ChatSummary,
+    message::{ChatMessage, ChatMessageContent},
+};
+use mongodb::bson::{self, doc, oid::ObjectId};
+
+use crate::{context::auxiliary::AuxiliaryModel, state::AppState};
+
+/// Number of dropped, not yet summarized messages that triggers a new summary.
+const MIN_UNSUMMARIZED_MESSAGES: usize = 4;
+
+/// System message that stands in for history dropped from the context window.
+pub fn context_message(summary: &ChatSummary) -> OpenAIMessage {
+    OpenAIMessage {
+        role: "system".to_string(),
+        content: vec![OpenAIMessageContent::Text {
+            text: format!(
+                "Summary of the earlier part of this conversation, which is no longer shown in full:\n{}",
+                summary.content
+            ),
+        }],
+    }
+}
+
+/// Whether the messages dropped from the context window warrant refreshing the summary.
+pub fn is_stale(summary: Option<&ChatSummary>, dropped: &[ChatMessage]) -> bool {
+    if summary.is_none() {
+        return !dropped.is_empty();
+    }
+
+    let unsummarized = dropped
+        .iter()
+        .filter(|message| summary.is_none_or(|summary| message.timestamp > summary.covers_until))
+        .count();
+
+    unsummarized >= MIN_UNSUMMARIZED_MESSAGES
+}
+
+/// Folds `dropped` into the chat's running summary, unless another update replaced `previous`
+/// in the meantime.
+pub async fn update(
+    state: &AppState,
+    auxiliary: &AuxiliaryModel,
+    chat_id: ObjectId,
+    previous: Option<ChatSummary>,
+    dropped: Vec<ChatMessage>,
+) -> anyhow::Result<()> {
+    let new_messages: Vec<&ChatMessage> = dropped
+        .iter()
+        .filter(|message| {
+            previous
+                .as_ref()
+                .is_none_or(|summary| message.timestamp > summary.covers_until)
+        })
+        .collect();
+    let Some(covers_until) = new_messages.last().map(|message| message.timestamp) else {
+        return Ok(());
+    };
+
+    let transcript: String = new_messages
+        .iter()
+        .map(|message| format!("{}: {}\n", message.role, transcript_text(message)))
+        .collect();
+
+    let prompt = format!(
+        "You maintain a running summary of a conversation between a user and an AI assistant.
+Update the existing summary with the new messages. Keep facts, decisions, names, numbers, code identifiers and open questions; drop greetings and filler.
+The summary must be written in third person, be at most 300 words and contain no preamble.
+
+Existing summary: {}
+
+New messages:
+{transcript}
+Updated summary:",
+        previous
+            .as_ref()
+            .map(|summary| summary.content.as_str())
+            .unwrap_or("None yet."),
+    );
+
+    let content = auxiliary
+        .client
+        .clone()
+        .chat_completion_non_streaming(
+            auxiliary.model.clone(),
+            vec![OpenAIMessage {
+                role: "user".to_string(),
+                content: vec![OpenAIMessageContent::Text { text: prompt }],
+            }],
+            CompletionParameters {
+                temperature: Some(0.2),
+                max_tokens: Some(2000),
+                ..Default::default()
+            },
+            None,
+        )
+        .await?;
+    let content = structured::strip_reasoning(&content).to_string();
+    if content.is_empty() {
+        anyhow::bail!("Summary was cut off while reasoning");
+    }
+
+    let summary = ChatSummary {
+        content,
+        covers_until,
+        updated_at: Utc::now(),
+    };
+
+    // only replaces the summary this one was built on, so a concurrent update is not lost
+    let filter = match &previous {
+        Some(previous) => doc! {
+            "_id": chat_id,
+            "summary.covers_until": bson::DateTime::from_chrono(previous.covers_until),
+        },
+        None => doc! { "_id": chat_id, "summary": null },
+    };
+    let result = state
+        .storage()
+        .database()
+        .chats
+        .collection()
+        .update_one(
+            filter,
+            doc! { "$set": { "summary": bson::to_bson(&summary)? } },
+        )
+        .await?;
+    if result.matched_count == 0 {
+        tracing::debug!("Summary of chat {chat_id} was updated concurrently, keeping it.");
+    }
+
+    Ok(())
+}
+
+pub fn transcript_text(message: &ChatMessage) -> String {
+    message
+        .content
+        .iter()
+        .map(|content| match content {
+            ChatMessageContent::Text { value } => value.clone(),
+            ChatMessageContent::Image { .. } => "[image]".to_string(),
+            ChatMessageContent::Pdf { .. } => "[pdf]".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,138 @@
+use ai::openai::structured::StructuredRequest;
+use model::message::ChatMessage;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::context::{auxiliary::AuxiliaryModel, summary};
+
+/// Longest chat name accepted from the model.
+const MAX_TITLE_CHARS: usize = 80;
+/// Messages from the start of a chat a regenerated name is based on.
+pub const TITLE_CONTEXT_MESSAGES: i64 = 6;
+/// Recent messages checked for topic drift.
+pub const DRIFT_CONTEXT_MESSAGES: usize = 8;
+/// Characters of each message included in title prompts.
+const MAX_MESSAGE_CHARS: usize = 500;
+
+const EXAMPLES: &str = "Here are some examples of first messages and their chat names:\n\ninput: I need help choosing a new laptop for college.\noutput: Laptop Recommendations for College\n\ninput:  Best places to eat Italian food in downtown Chicago?\noutput: Chicago Italian Food Guide";
+const RULES: &str = "The name must be a SINGLE, SHORT sentence. Do not include any parentheses, other symbols or any words except for the final result.";
+
+#[derive(Debug, Deserialize)]
+struct GeneratedTitle {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DriftCheck {
+    drifted: bool,
+    title: Option<String>,
+}
+
+/// Names a chat after its first message.
+pub async fn generate(auxiliary: &AuxiliaryModel, first_message: &str) -> anyhow::Result<String> {
+    let prompt = format!(
+        "{EXAMPLES}\n\nNow, generate a descriptive name for a chat where the first message was: \"{first_message}\"\n{RULES}"
+    );
+
+    title_completion(auxiliary, prompt).await
+}
+
+/// Names a chat after its first exchanges.
+pub async fn from_messages(
+    auxiliary: &AuxiliaryModel,
+    messages: &[ChatMessage],
+) -> anyhow::Result<String> {
+    let prompt = format!(
+        "{EXAMPLES}\n\nNow, generate a descriptive name for a chat that started like this:\n{}\n{RULES}",
+        transcript(messages)
+    );
+
+    title_completion(auxiliary, prompt).await
+}
+
+/// A new name for the chat if its recent messages moved away from the topic of `current`.
+pub async fn drifted(
+    auxiliary: &AuxiliaryModel,
+    current: &str,
+    recent: &[ChatMessage],
+) -> anyhow::Result<Option<String>> {
+    let prompt = format!(
+        "A chat is named \"{current}\". Here are its most recent messages:\n{}\nDecide whether the conversation moved to a different topic than its name describes. Minor follow-ups or subtopics are not a different topic.\nIf it did, set `drifted` to true and `title` to a new descriptive name. {RULES}\nOtherwise set `drifted` to false and `title` to null.",
+        transcript(recent)
+    );
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "drifted": { "type": "boolean" },
+            "title": { "type": ["string", "null"] },
+        },
+        "required": ["drifted", "title"],
+        "additionalProperties": false,
+    });
+    let request = StructuredRequest {
+        temperature: Some(0.),
+        max_tokens: Some(1000),
+        ..StructuredRequest::new("chat_title_drift", schema, prompt)
+    };
+
+    auxiliary
+        .client
+        .clone()
+        .structured_completion(
+            auxiliary.model.clone(),
+            request,
+            |DriftCheck { drifted, title }| {
+                if !drifted {
+                    return Ok(None);
+                }
+                let title = clean(&title.unwrap_or_default())?;
+                Ok((title != current).then_some(title))
+            },
+        )
+        .await
+}
+
+async fn title_completion(auxiliary: &AuxiliaryModel, prompt: String) -> anyhow::Result<String> {
+    let schema = json!({
+        "type": "object",
+        "properties": { "title": { "type": "string" } },
+        "required": ["title"],
+        "additionalProperties": false,
+    });
+    let request = StructuredRequest {
+        temperature: Some(0.),
+        max_tokens: Some(1000),
+        ..StructuredRequest::new("chat_title", schema, prompt)
+    };
+
+    auxiliary
+        .client
+        .clone()
+        .structured_completion(
+            auxiliary.model.clone(),
+            request,
+            |GeneratedTitle { title }| clean(&title),
+        )
+        .await
+}
+
+fn clean(title: &str) -> anyhow::Result<String> {
+    let title = title.trim().trim_matches(['"', '\'', '.']).trim();
+    if title.is_empty() || title.chars().count() > MAX_TITLE_CHARS {
+        anyhow::bail!("The title must be 1-{MAX_TITLE_CHARS} chars long");
+    }
+    Ok(title.to_string())
+}
+
+fn transcript(messages: &[ChatMessage]) -> String {
+    messages
+        .iter()
+        .map(|message| {
+            let text: String = summary::transcript_text(message)
+                .chars()
+                .take(MAX_MESSAGE_CHARS)
+                .collect();
+            format!("{}: {text}\n", message.role)
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+use ai::openai::completions::{OpenAIMessage, OpenAIMessageContent};
+
+/// Role markers and separators added around every message.
+const MESSAGE_OVERHEAD: u32 = 4;
+/// Providers bill a single image at roughly this many tokens.
+const IMAGE_TOKENS: u32 = 1_000;
+/// Parsed PDFs vary wildly; this assumes a few pages of text.
+const FILE_TOKENS: u32 = 2_000;
+
+/// Estimates token counts without running the model's tokenizer.
+///
+/// ASCII text is divided by a per-family characters-per-token ratio measured on English chat
+/// transcripts. Other scripts (CJK, Cyrillic, emoji) are usually split into one or more tokens
+/// per character, so every non-ASCII character is counted as a token of its own.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenEstimator {
+    chars_per_token: f32,
+}
+
+impl TokenEstimator {
+    pub fn for_model(identifier: &str) -> Self {
+        let family = identifier.split('/').next().unwrap_or_default();
+        let chars_per_token = match family {
+            "openai" => 4.0,
+            "anthropic" => 3.5,
+            "google" => 4.0,
+            "meta-llama" | "nvidia" | "perplexity" => 3.8,
+            "deepseek" | "tngtech" => 3.6,
+            "qwen" => 3.3,
+            _ => 3.5,
+        };
+
+        Self { chars_per_token }
+    }
+
+    pub fn text(&self, text: &str) -> u32 {
+        let (ascii, other) = text.chars().fold((0u32, 0u32), |(ascii, other), c| {
+            if c.is_ascii() {
+                (ascii + 1, other)
+            } else {
+                (ascii, other + 1)
+            }
+        });
+
+        (ascii as f32 / self.chars_per_token).ceil() as u32 + other
+    }
+
+    pub fn message(&self, message: &OpenAIMessage) -> u32 {
+        MESSAGE_OVERHEAD
+            + message
+                .content
+                .iter()
+                .map(|content| match content {
+                    OpenAIMessageContent::Text { text } => self.text(text),
+                    OpenAIMessageContent::ImageUrl { .. } => IMAGE_TOKENS,
+                    OpenAIMessageContent::File { .. } => FILE_TOKENS,
+                })
+                .sum::<u32>()
+    }
+
+    pub fn messages(&self, messages: &[OpenAIMessage]) -> u32 {
+        messages.iter().map(|message| self.message(message)).sum()
+    }
+
+    pub fn attachments(&self, images: usize, files: usize) -> u32 {
+        images as u32 * IMAGE_TOKENS + files as u32 * FILE_TOKENS
+    }
+}
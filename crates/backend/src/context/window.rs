@@ -0,0 +1,70 @@
+use ai::openai::completions::OpenAIMessage;
+
+use crate::{context::tokens::TokenEstimator, models::Model};
+
+/// Room left for the completion when the model does not declare its output limit.
+const DEFAULT_OUTPUT_RESERVE: u32 = 4_096;
+
+pub struct ContextWindow {
+    pub messages: Vec<OpenAIMessage>,
+    /// Number of history messages that did not fit and were left out.
+    pub dropped: usize,
+}
+
+/// Prompt tokens available for a model once space for its reply has been set aside.
+pub fn prompt_budget(model: &Model) -> u32 {
+    let reserve = model
+        .capabilities
+        .max_output
+        .unwrap_or(DEFAULT_OUTPUT_RESERVE)
+        .min(model.context_length / 4);
+
+    model.context_length - reserve
+}
+
+/// Drops the oldest turns until `messages` fit into `budget` tokens.
+///
+/// Leading system messages and the final message (the turn being answered) are always kept.
+/// History is cut at user messages, so the model never sees a reply without its question.
+pub fn fit(
+    mut messages: Vec<OpenAIMessage>,
+    budget: u32,
+    estimator: &TokenEstimator,
+) -> ContextWindow {
+    let system = messages
+        .iter()
+        .take_while(|message| message.role == "system")
+        .count();
+    let Some(latest) = messages.pop() else {
+        return ContextWindow {
+            messages,
+            dropped: 0,
+        };
+    };
+
+    let history = messages.split_off(system);
+    let mut used = estimator.messages(&messages) + estimator.message(&latest);
+
+    // walk history from the newest message, keeping as much as fits
+    let mut start = history.len();
+    for (index, message) in history.iter().enumerate().rev() {
+        let cost = estimator.message(message);
+        if used + cost > budget {
+            break;
+        }
+        used += cost;
+        start = index;
+    }
+    while history
+        .get(start)
+        .is_some_and(|message| message.role != "user")
+    {
+        start += 1;
+    }
+
+    let dropped = start;
+    messages.extend(history.into_iter().skip(start));
+    messages.push(latest);
+
+    ContextWindow { messages, dropped }
+}
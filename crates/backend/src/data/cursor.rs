@@ -0,0 +1,54 @@
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use mongodb::bson::{self, Document, doc, oid::ObjectId};
+
+/// Position in a listing sorted by a timestamp and then by id, both descending. Encoded as an
+/// opaque string for clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub timestamp: DateTime<Utc>,
+    pub id: ObjectId,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(format!(
+            "{}.{}",
+            self.timestamp.timestamp_millis(),
+            self.id.to_hex()
+        ))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(BASE64_URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (millis, id) = decoded.split_once('.')?;
+
+        Some(Self {
+            timestamp: DateTime::from_timestamp_millis(millis.parse().ok()?)?,
+            id: ObjectId::parse_str(id).ok()?,
+        })
+    }
+
+    /// Filter matching the documents listed after the cursor, i.e. older ones, where `field`
+    /// holds the timestamp.
+    pub fn older(&self, field: &str) -> Document {
+        let timestamp = bson::DateTime::from_chrono(self.timestamp);
+        doc! {
+            "$or": [
+                { field: { "$lt": timestamp } },
+                { field: timestamp, "_id": { "$lt": self.id } },
+            ]
+        }
+    }
+
+    /// Filter matching the documents listed before the cursor, i.e. newer ones.
+    pub fn newer(&self, field: &str) -> Document {
+        let timestamp = bson::DateTime::from_chrono(self.timestamp);
+        doc! {
+            "$or": [
+                { field: { "$gt": timestamp } },
+                { field: timestamp, "_id": { "$gt": self.id } },
+            ]
+        }
+    }
+}
@@ -1 +1,4 @@
+pub mod cursor;
 pub mod mongodb;
+pub mod tags;
+pub mod transaction;
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use mongodb::{
-    Client,
+    Client, Collection,
     bson::{Document, doc, oid::ObjectId},
 };
 use serde::{Deserialize, Serialize};
@@ -22,6 +22,12 @@ impl<Entity: Serialize + for<'a> Deserialize<'a> + Send + Sync> MongoDataAdapter
             d: PhantomData,
         }
     }
+
+    pub fn collection(&self) -> Collection<Entity> {
+        self.client
+            .database(&self.db)
+            .collection::<Entity>(&self.collection)
+    }
 }
 
 impl<Entity: Serialize + for<'a> Deserialize<'a> + Send + Sync> MongoDataAdapter<Entity> {
@@ -90,4 +96,22 @@ impl<Entity: Serialize + for<'a> Deserialize<'a> + Send + Sync> MongoDataAdapter
 
         Ok(())
     }
+    pub async fn create_many(&self, entities: Vec<Entity>) -> anyhow::Result<()> {
+        self.client
+            .database(&self.db)
+            .collection::<Entity>(&self.collection)
+            .insert_many(entities)
+            .await?;
+
+        Ok(())
+    }
+    pub async fn delete_many(&self, doc: Document) -> anyhow::Result<()> {
+        self.client
+            .database(&self.db)
+            .collection::<Entity>(&self.collection)
+            .delete_many(doc)
+            .await?;
+
+        Ok(())
+    }
 }
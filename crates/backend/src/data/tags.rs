@@ -0,0 +1,25 @@
+use validator::ValidationError;
+
+/// Checks that every tag is 1-32 chars long once trimmed.
+pub fn validate(tags: &[String]) -> Result<(), ValidationError> {
+    if tags
+        .iter()
+        .all(|tag| (1..=32).contains(&tag.trim().chars().count()))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("tags").with_message("Tags must be 1-32 chars long.".into()))
+    }
+}
+
+/// Trimmed, lowercased tags without duplicates, in the order given.
+pub fn normalize(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
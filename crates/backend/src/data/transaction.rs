@@ -0,0 +1,42 @@
+use mongodb::{
+    ClientSession, Collection,
+    bson::Document,
+    error::{Error, ErrorKind},
+};
+
+/// Server error code for operations a standalone server does not support, like transactions.
+const ILLEGAL_OPERATION: i32 = 20;
+
+/// Whether the server rejected a transaction because it is not a replica set.
+pub fn unsupported(error: &Error) -> bool {
+    matches!(*error.kind, ErrorKind::Command(ref error) if error.code == ILLEGAL_OPERATION)
+}
+
+/// Deletes the matching documents, within `session` when one is given.
+pub async fn delete_many<T: Send + Sync>(
+    collection: &Collection<T>,
+    filter: Document,
+    session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<u64> {
+    let action = collection.delete_many(filter);
+    let result = match session {
+        Some(session) => action.session(session).await?,
+        None => action.await?,
+    };
+    Ok(result.deleted_count)
+}
+
+/// Updates the matching documents, within `session` when one is given.
+pub async fn update_many<T: Send + Sync>(
+    collection: &Collection<T>,
+    filter: Document,
+    update: Document,
+    session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<u64> {
+    let action = collection.update_many(filter, update);
+    let result = match session {
+        Some(session) => action.session(session).await?,
+        None => action.await?,
+    };
+    Ok(result.modified_count)
+}
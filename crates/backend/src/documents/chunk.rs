@@ -0,0 +1,60 @@
+/// Target size of a chunk. Small enough for several to fit into the prompt next to the chat.
+const CHUNK_CHARS: usize = 1_200;
+/// Text repeated at the start of the next chunk, so a passage cut in half is found in either.
+const OVERLAP_CHARS: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct TextChunk {
+    /// 1-based page the chunk starts on.
+    pub page: u32,
+    pub content: String,
+}
+
+/// Splits extracted pages into overlapping chunks on word boundaries.
+pub fn split(pages: &[String]) -> Vec<TextChunk> {
+    let words = pages.iter().enumerate().flat_map(|(page, text)| {
+        text.split_whitespace()
+            .map(move |word| (page as u32 + 1, word))
+    });
+
+    let mut chunks = Vec::new();
+    let mut current: Vec<(u32, &str)> = Vec::new();
+    let mut length = 0;
+    for (page, word) in words {
+        if length + word.len() > CHUNK_CHARS && !current.is_empty() {
+            chunks.push(to_chunk(&current));
+
+            // carry the tail of the finished chunk over as overlap
+            let mut overlap = 0;
+            let keep = current
+                .iter()
+                .rev()
+                .take_while(|(_, word)| {
+                    overlap += word.len() + 1;
+                    overlap <= OVERLAP_CHARS
+                })
+                .count();
+            current.drain(..current.len() - keep);
+            length = current.iter().map(|(_, word)| word.len() + 1).sum();
+        }
+
+        length += word.len() + 1;
+        current.push((page, word));
+    }
+    if !current.is_empty() {
+        chunks.push(to_chunk(&current));
+    }
+
+    chunks
+}
+
+fn to_chunk(words: &[(u32, &str)]) -> TextChunk {
+    TextChunk {
+        page: words[0].0,
+        content: words
+            .iter()
+            .map(|(_, word)| *word)
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
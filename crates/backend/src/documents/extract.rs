@@ -0,0 +1,6 @@
+use anyhow::Context;
+
+/// Extracts the text of every page of a PDF.
+pub fn pdf_pages(contents: &[u8]) -> anyhow::Result<Vec<String>> {
+    pdf_extract::extract_text_from_mem_by_pages(contents).context("Failed to extract PDF text")
+}
@@ -0,0 +1,161 @@
+pub mod chunk;
+pub mod extract;
+pub mod retrieve;
+
+use std::sync::Arc;
+
+use futures::AsyncReadExt;
+use model::{
+    document::DocumentChunk,
+    upload::{IngestionStatus, UserUpload},
+    vector::{VectorNamespace, VectorRecord},
+};
+use mongodb::{
+    ClientSession,
+    bson::{Bson, Document, doc, oid::ObjectId},
+};
+
+use crate::{
+    data::transaction,
+    state::{AppState, storage::bucket::BUCKET_NAME},
+};
+
+/// Whether uploads of this type are indexed for retrieval.
+pub fn is_indexable(content_type: &str) -> bool {
+    content_type == "application/pdf"
+}
+
+/// Extracts, chunks and embeds an upload in the background.
+pub fn spawn_ingestion(state: Arc<AppState>, upload: UserUpload) {
+    tokio::spawn(async move {
+        let status = match ingest(&state, &upload).await {
+            Ok(()) => IngestionStatus::Indexed,
+            Err(e) => {
+                tracing::error!("Failed to ingest upload {}: {e}", upload.id);
+                // a partial index is worse than none, since it silently misses passages
+                let _ = remove(&state, upload.id).await;
+                IngestionStatus::Failed
+            }
+        };
+
+        if let Err(e) = set_status(&state, upload.id, status).await {
+            tracing::error!("Failed to update ingestion status of {}: {e}", upload.id);
+        }
+    });
+}
+
+pub async fn set_status(
+    state: &AppState,
+    upload_id: ObjectId,
+    status: IngestionStatus,
+) -> anyhow::Result<()> {
+    state
+        .storage()
+        .database()
+        .uploads
+        .update(
+            upload_id,
+            doc! { "$set": { "ingestion": mongodb::bson::to_bson(&status)? } },
+        )
+        .await
+}
+
+/// Removes the chunks of an upload and their embeddings.
+pub async fn remove(state: &AppState, upload_id: ObjectId) -> anyhow::Result<()> {
+    state
+        .storage()
+        .vectors()
+        .delete_group(VectorNamespace::Documents, upload_id)
+        .await?;
+    state
+        .storage()
+        .database()
+        .chunks
+        .delete_many(doc! { "upload_id": upload_id })
+        .await
+}
+
+/// Deletes the uploads with their chunks and stored files, within `session` when one is given.
+/// Embeddings live outside MongoDB and are removed separately with [`remove`].
+pub async fn delete_rows(
+    state: &AppState,
+    upload_ids: &[ObjectId],
+    mut session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<()> {
+    let database = state.storage().database();
+    let files = database.client().database("chat");
+
+    transaction::delete_many(
+        &database.chunks.collection(),
+        doc! { "upload_id": { "$in": upload_ids } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    transaction::delete_many(
+        &files.collection::<Document>(&format!("{BUCKET_NAME}.chunks")),
+        doc! { "files_id": { "$in": upload_ids } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    transaction::delete_many(
+        &files.collection::<Document>(&format!("{BUCKET_NAME}.files")),
+        doc! { "_id": { "$in": upload_ids } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    transaction::delete_many(
+        &database.uploads.collection(),
+        doc! { "_id": { "$in": upload_ids } },
+        session,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn ingest(state: &AppState, upload: &UserUpload) -> anyhow::Result<()> {
+    let mut file = state
+        .storage()
+        .bucket()
+        .gridfs()
+        .open_download_stream(Bson::ObjectId(upload.id))
+        .await?;
+    let mut contents = vec![];
+    file.read_to_end(&mut contents).await?;
+
+    let pages = tokio::task::spawn_blocking(move || extract::pdf_pages(&contents)).await??;
+    let chunks = chunk::split(&pages)
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| DocumentChunk {
+            id: Some(ObjectId::new()),
+            upload_id: upload.id,
+            user_id: upload.user_id,
+            index: index as u32,
+            page: Some(chunk.page),
+            content: chunk.content,
+        })
+        .collect::<Vec<_>>();
+    if chunks.is_empty() {
+        return Ok(());
+    }
+
+    let embeddings = state
+        .inference()
+        .embed(chunks.iter().map(|chunk| chunk.content.clone()).collect())
+        .await?;
+    let records = chunks
+        .iter()
+        .zip(embeddings)
+        .map(|(chunk, vector)| VectorRecord {
+            id: chunk.id.unwrap(),
+            namespace: VectorNamespace::Documents,
+            user_id: upload.user_id,
+            group: Some(upload.id),
+            vector,
+        })
+        .collect();
+
+    state.storage().vectors().upsert(records).await?;
+    state.storage().database().chunks.create_many(chunks).await
+}
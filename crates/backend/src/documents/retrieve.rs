@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use futures::TryStreamExt;
+use model::{document::DocumentChunk, message::Citation, vector::VectorNamespace};
+use mongodb::bson::{doc, oid::ObjectId};
+
+use crate::{
+    context::tokens::TokenEstimator,
+    state::{AppState, storage::vectors::VectorQuery},
+};
+
+/// Most chunks added to a single turn.
+const TOP_K: usize = 6;
+/// Chunks less similar than this are noise rather than context.
+const MIN_SCORE: f32 = 0.25;
+/// Upper bound on prompt tokens spent on retrieved passages.
+const MAX_CONTEXT_TOKENS: u32 = 3_000;
+/// Length of the passage preview returned with a citation.
+const SNIPPET_CHARS: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct RetrievedChunk {
+    pub chunk: DocumentChunk,
+    pub score: f32,
+}
+
+/// Finds the chunks of `upload_ids` most similar to `query`, within the context budget.
+pub async fn retrieve(
+    state: &AppState,
+    upload_ids: &[ObjectId],
+    query: &str,
+    estimator: &TokenEstimator,
+) -> anyhow::Result<Vec<RetrievedChunk>> {
+    if upload_ids.is_empty() || query.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let vector = state
+        .inference()
+        .embed(vec![query.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("Embeddings API returned no vector"))?;
+    let matches = state
+        .storage()
+        .vectors()
+        .query(VectorQuery {
+            namespace: VectorNamespace::Documents,
+            vector,
+            user_id: None,
+            groups: Some(upload_ids.to_vec()),
+            limit: TOP_K,
+            min_score: MIN_SCORE,
+        })
+        .await?;
+    if matches.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let ids: Vec<ObjectId> = matches.iter().map(|matched| matched.id).collect();
+    let mut chunks: HashMap<ObjectId, DocumentChunk> = state
+        .storage()
+        .database()
+        .chunks
+        .get_many(doc! { "_id": { "$in": ids } })
+        .await?
+        .map_ok(|chunk| (chunk.id.unwrap(), chunk))
+        .try_collect()
+        .await?;
+    let scored = matches.into_iter().filter_map(|matched| {
+        Some(RetrievedChunk {
+            chunk: chunks.remove(&matched.id)?,
+            score: matched.score,
+        })
+    });
+
+    let mut used = 0;
+    Ok(scored
+        .take_while(|retrieved| {
+            used += estimator.text(&retrieved.chunk.content);
+            used <= MAX_CONTEXT_TOKENS
+        })
+        .collect())
+}
+
+/// Numbered excerpts the model is asked to cite as `[n]`.
+pub fn context_text(chunks: &[RetrievedChunk]) -> String {
+    let excerpts: String = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, retrieved)| {
+            format!(
+                "[{}] (file {}, page {}):\n{}\n\n",
+                index + 1,
+                retrieved.chunk.upload_id.to_hex(),
+                retrieved
+                    .chunk
+                    .page
+                    .map(|page| page.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                retrieved.chunk.content
+            )
+        })
+        .collect();
+
+    format!(
+        "Relevant excerpts from the user's documents. Cite them as [n] where you use them, and ignore the ones that do not apply:\n\n{excerpts}"
+    )
+}
+
+pub fn citations(chunks: &[RetrievedChunk]) -> Vec<Citation> {
+    chunks
+        .iter()
+        .map(|retrieved| Citation {
+            upload_id: retrieved.chunk.upload_id,
+            chunk_id: retrieved.chunk.id.unwrap(),
+            page: retrieved.chunk.page,
+            snippet: retrieved
+                .chunk
+                .content
+                .chars()
+                .take(SNIPPET_CHARS)
+                .collect(),
+            score: retrieved.score,
+        })
+        .collect()
+}
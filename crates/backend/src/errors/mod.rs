@@ -21,11 +21,33 @@ pub enum ApplicationError {
 
     #[error("Invalid model identifier.")]
     InvalidModelIdentifier,
+    #[error("Model is currently unavailable.")]
+    ModelUnavailable,
+    #[error("Model does not support image input.")]
+    ModelDoesNotSupportImages,
+    #[error("Model does not support PDF input.")]
+    ModelDoesNotSupportPdf,
+    #[error("Chat exceeds the model's context window.")]
+    ContextLengthExceeded,
     #[error("Invalid chat share link.")]
     InvalidShareLink,
+    #[error("Chat has no summary yet.")]
+    ChatHasNoSummary,
+    #[error("Chat has no messages yet.")]
+    ChatHasNoMessages,
+    #[error("No model is available to generate the chat name.")]
+    AuxiliaryModelUnavailable,
+    #[error("Failed to generate the chat name.")]
+    TitleGenerationFailed,
+    #[error("Invalid pagination cursor.")]
+    InvalidCursor,
+    #[error("Temporary chats do not accept attachments.")]
+    TemporaryChatAttachments,
 
     #[error("Upload not found.")]
     UploadNotFound,
+    #[error("Upload is already a knowledge file of another assistant.")]
+    UploadPinnedToAnotherAssistant,
 
     #[error("File required.")]
     FileRequired,
@@ -38,6 +60,8 @@ pub enum ApplicationError {
 
     #[error("Invalid inference provider.")]
     InvalidInferenceProvider,
+    #[error("The model's inference provider is not configured.")]
+    InferenceProviderNotConfigured,
 
     #[error("Key does not exist.")]
     KeyDoesNotExist,
@@ -48,6 +72,21 @@ pub enum ApplicationError {
     MemoryDoesNotExist,
     #[error("Memory does not belong to the user.")]
     MemoryDoesNotBelongToUser,
+
+    #[error("Assistant does not exist.")]
+    AssistantDoesNotExist,
+    #[error("Assistant does not belong to the user.")]
+    AssistantDoesNotBelongToUser,
+
+    #[error("Project does not exist.")]
+    ProjectDoesNotExist,
+    #[error("Project does not belong to the user.")]
+    ProjectDoesNotBelongToUser,
+
+    #[error("Folder does not exist.")]
+    FolderDoesNotExist,
+    #[error("Folder does not belong to the user.")]
+    FolderDoesNotBelongToUser,
 }
 
 impl IntoResponse for ApplicationError {
@@ -58,18 +97,40 @@ impl IntoResponse for ApplicationError {
             Self::ValidationError(errors) => {
                 (StatusCode::BAD_REQUEST, Json(json!({ "error": errors }))).into_response()
             }
+            Self::TitleGenerationFailed => (
+                StatusCode::BAD_GATEWAY,
+                Json(json!({ "error": self.to_string() })),
+            )
+                .into_response(),
             Self::InvalidModelIdentifier
+            | Self::ModelUnavailable
+            | Self::ModelDoesNotSupportImages
+            | Self::ModelDoesNotSupportPdf
+            | Self::ContextLengthExceeded
             | Self::InvalidShareLink
+            | Self::ChatHasNoSummary
+            | Self::ChatHasNoMessages
+            | Self::AuxiliaryModelUnavailable
+            | Self::InvalidCursor
+            | Self::TemporaryChatAttachments
             | Self::UploadNotFound
+            | Self::UploadPinnedToAnotherAssistant
             | Self::FileRequired
             | Self::NoFileContentType
             | Self::InvalidFileContentType
             | Self::FileTooLarge
             | Self::InvalidInferenceProvider
+            | Self::InferenceProviderNotConfigured
             | Self::KeyDoesNotExist
             | Self::KeyDoesNotBelongToUser
             | Self::MemoryDoesNotExist
-            | Self::MemoryDoesNotBelongToUser => (
+            | Self::MemoryDoesNotBelongToUser
+            | Self::AssistantDoesNotExist
+            | Self::AssistantDoesNotBelongToUser
+            | Self::ProjectDoesNotExist
+            | Self::ProjectDoesNotBelongToUser
+            | Self::FolderDoesNotExist
+            | Self::FolderDoesNotBelongToUser => (
                 StatusCode::BAD_REQUEST,
                 Json(json!({ "error": self.to_string() })),
             )
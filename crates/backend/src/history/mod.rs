@@ -0,0 +1,224 @@
+pub mod semantic;
+pub mod snippet;
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use model::{
+    chat::Chat,
+    message::{ChatMessage, ChatMessageContent},
+};
+use mongodb::bson::{self, Document, doc, oid::ObjectId};
+use serde::Deserialize;
+
+use crate::{data::cursor::Cursor, state::AppState};
+
+/// Matching messages returned per chat.
+const MESSAGES_PER_CHAT: i32 = 3;
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Model identifier or display name the matching messages were written by.
+    pub model: Option<String>,
+}
+
+/// Chat found by a search, with its best matching messages.
+#[derive(Debug, Clone)]
+pub struct ChatMatch {
+    pub chat: Chat,
+    /// Newest matching messages, newest first.
+    pub messages: Vec<ChatMessage>,
+    /// Time of the newest matching message, or of the chat when only its name matched. Results
+    /// are ordered by it.
+    pub activity: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageGroup {
+    #[serde(rename = "_id")]
+    chat_id: ObjectId,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    activity: DateTime<Utc>,
+    messages: Vec<ChatMessage>,
+}
+
+/// Finds the user's chats whose messages or name match a MongoDB text search query, most
+/// recently active first. Returns a page of at most `limit` chats and the cursor of the next one.
+pub async fn text_search(
+    state: &AppState,
+    user_id: ObjectId,
+    query: &str,
+    filters: &SearchFilters,
+    cursor: Option<Cursor>,
+    limit: usize,
+) -> anyhow::Result<(Vec<ChatMatch>, Option<Cursor>)> {
+    let database = state.storage().database();
+
+    let chat_ids: Vec<ObjectId> = database
+        .chats
+        .collection()
+        .distinct("_id", doc! { "user_id": user_id, "deleted_at": null })
+        .await?
+        .into_iter()
+        .filter_map(|id| id.as_object_id())
+        .collect();
+    if chat_ids.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+
+    let mut message_filter = doc! {
+        "$text": { "$search": query },
+        "chat_id": { "$in": &chat_ids },
+    };
+    if let Some(range) = time_range(filters) {
+        message_filter.insert("timestamp", range);
+    }
+    if let Some(ref model) = filters.model {
+        message_filter.insert("model", doc! { "$in": model_names(state, model) });
+    }
+
+    let mut pipeline = vec![
+        doc! { "$match": message_filter.clone() },
+        doc! { "$sort": { "timestamp": -1, "_id": -1 } },
+        // only the newest few matches of a chat are kept, however many there are
+        doc! { "$group": {
+            "_id": "$chat_id",
+            "activity": { "$first": "$timestamp" },
+            "messages": { "$firstN": { "input": "$$ROOT", "n": MESSAGES_PER_CHAT } },
+        } },
+    ];
+    if let Some(ref cursor) = cursor {
+        pipeline.push(doc! { "$match": cursor.older("activity") });
+    }
+    pipeline.push(doc! { "$sort": { "activity": -1, "_id": -1 } });
+    pipeline.push(doc! { "$limit": limit as i64 + 1 });
+
+    let groups: Vec<MessageGroup> = database
+        .messages
+        .collection()
+        .aggregate(pipeline)
+        .with_type::<MessageGroup>()
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut matches: Vec<(ObjectId, DateTime<Utc>, Vec<ChatMessage>)> = groups
+        .into_iter()
+        .map(|group| (group.chat_id, group.activity, group.messages))
+        .collect();
+
+    // a model filter only applies to messages, so chat names cannot match it
+    if filters.model.is_none() {
+        // chats with matching messages are ordered by them, not by their name
+        let with_messages: Vec<ObjectId> = database
+            .messages
+            .collection()
+            .aggregate(vec![
+                doc! { "$match": message_filter },
+                doc! { "$group": { "_id": "$chat_id" } },
+            ])
+            .await?
+            .try_collect::<Vec<Document>>()
+            .await?
+            .into_iter()
+            .filter_map(|group| group.get_object_id("_id").ok())
+            .collect();
+
+        let mut chat_filter = doc! {
+            "$text": { "$search": query },
+            "user_id": user_id,
+            "deleted_at": null,
+            "_id": { "$nin": with_messages },
+        };
+        if let Some(range) = time_range(filters) {
+            chat_filter.insert("timestamp", range);
+        }
+        if let Some(ref cursor) = cursor {
+            chat_filter.insert("$and", vec![cursor.older("timestamp")]);
+        }
+
+        let named: Vec<Chat> = database
+            .chats
+            .collection()
+            .find(chat_filter)
+            .sort(doc! { "timestamp": -1, "_id": -1 })
+            .limit(limit as i64 + 1)
+            .await?
+            .try_collect()
+            .await?;
+        matches.extend(
+            named
+                .into_iter()
+                .filter_map(|chat| Some((chat.id?, chat.timestamp, Vec::new()))),
+        );
+    }
+
+    matches.sort_by_key(|(id, timestamp, _)| std::cmp::Reverse((*timestamp, *id)));
+    let next_cursor = (matches.len() > limit).then(|| {
+        let (id, timestamp, _) = &matches[limit - 1];
+        Cursor {
+            timestamp: *timestamp,
+            id: *id,
+        }
+    });
+    matches.truncate(limit);
+
+    let ids: Vec<ObjectId> = matches.iter().map(|(id, ..)| *id).collect();
+    let mut chats: Vec<Chat> = database
+        .chats
+        .get_many(doc! { "_id": { "$in": ids } })
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok((
+        matches
+            .into_iter()
+            .filter_map(|(id, activity, messages)| {
+                let position = chats.iter().position(|chat| chat.id == Some(id))?;
+                Some(ChatMatch {
+                    chat: chats.swap_remove(position),
+                    messages,
+                    activity,
+                })
+            })
+            .collect(),
+        next_cursor,
+    ))
+}
+
+/// Text of a message that is covered by the text index.
+pub fn message_text(message: &ChatMessage) -> String {
+    message
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            ChatMessageContent::Text { value } => Some(value.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn time_range(filters: &SearchFilters) -> Option<Document> {
+    let mut range = Document::new();
+    if let Some(from) = filters.from {
+        range.insert("$gte", bson::DateTime::from_chrono(from));
+    }
+    if let Some(to) = filters.to {
+        range.insert("$lte", bson::DateTime::from_chrono(to));
+    }
+
+    (!range.is_empty()).then_some(range)
+}
+
+/// Values a message's `model` can hold for a model filter. Messages store the display name of the
+/// model they were written by, so identifiers are mapped to it.
+fn model_names(state: &AppState, model: &str) -> Vec<String> {
+    let mut names = vec![model.to_string()];
+    if let Some(model) = state.models().find(model) {
+        names.push(model.name.clone());
+    }
+    names
+}
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::TryStreamExt;
+use model::{
+    chat::Chat,
+    message::ChatMessage,
+    vector::{VectorNamespace, VectorRecord},
+};
+use mongodb::bson::{doc, oid::ObjectId};
+
+use crate::{
+    history::{MESSAGES_PER_CHAT, SearchFilters, message_text, model_names, time_range},
+    state::{AppState, storage::vectors::VectorQuery},
+};
+
+/// Characters of a message that are embedded. Long answers are represented by their beginning.
+const MAX_EMBEDDED_CHARS: usize = 8_000;
+/// Message matches fetched before they are grouped into chats.
+const CANDIDATES: usize = 200;
+/// Messages less similar than this are rarely about the query.
+const MIN_SCORE: f32 = 0.3;
+
+/// Chat found by similarity, with its messages closest to the query.
+#[derive(Debug, Clone)]
+pub struct SemanticMatch {
+    pub chat: Chat,
+    /// Matching messages, most similar first.
+    pub messages: Vec<(ChatMessage, f32)>,
+    /// Similarity of the best matching message. Results are ordered by it.
+    pub score: f32,
+}
+
+/// Embeds messages of a chat so they can be found by similarity. Messages without text are only
+/// marked as indexed.
+pub async fn index(
+    state: &AppState,
+    user_id: ObjectId,
+    messages: &[ChatMessage],
+) -> anyhow::Result<()> {
+    let (embedded, texts): (Vec<&ChatMessage>, Vec<String>) = messages
+        .iter()
+        .filter(|message| message.id.is_some())
+        .map(|message| {
+            let text: String = message_text(message)
+                .chars()
+                .take(MAX_EMBEDDED_CHARS)
+                .collect();
+            (message, text)
+        })
+        .filter(|(_, text)| !text.trim().is_empty())
+        .unzip();
+
+    if !texts.is_empty() {
+        let vectors = state.inference().embed(texts).await?;
+        if vectors.len() != embedded.len() {
+            anyhow::bail!("Embeddings API returned {} vectors", vectors.len());
+        }
+
+        state
+            .storage()
+            .vectors()
+            .upsert(
+                embedded
+                    .into_iter()
+                    .zip(vectors)
+                    .map(|(message, vector)| VectorRecord {
+                        id: message.id.unwrap(),
+                        namespace: VectorNamespace::Messages,
+                        user_id,
+                        group: Some(message.chat_id),
+                        vector,
+                    })
+                    .collect(),
+            )
+            .await?;
+    }
+
+    let ids: Vec<ObjectId> = messages.iter().filter_map(|message| message.id).collect();
+    state
+        .storage()
+        .database()
+        .messages
+        .collection()
+        .update_many(
+            doc! { "_id": { "$in": ids } },
+            doc! { "$set": { "indexed": true } },
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Removes the embeddings of a chat's messages.
+pub async fn remove_chat(state: &AppState, chat_id: ObjectId) -> anyhow::Result<()> {
+    state
+        .storage()
+        .vectors()
+        .delete_group(VectorNamespace::Messages, chat_id)
+        .await
+}
+
+/// Finds the user's chats with messages most similar to `query`, ranked by their best matching
+/// message.
+pub async fn search(
+    state: &AppState,
+    user_id: ObjectId,
+    query: &str,
+    filters: &SearchFilters,
+    limit: usize,
+) -> anyhow::Result<Vec<SemanticMatch>> {
+    let vector = state
+        .inference()
+        .embed(vec![query.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("Embeddings API returned no vector"))?;
+    let matches = state
+        .storage()
+        .vectors()
+        .query(VectorQuery {
+            namespace: VectorNamespace::Messages,
+            vector,
+            user_id: Some(user_id),
+            groups: None,
+            limit: CANDIDATES,
+            min_score: MIN_SCORE,
+        })
+        .await?;
+    if matches.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut filter = doc! {
+        "_id": { "$in": matches.iter().map(|matched| matched.id).collect::<Vec<_>>() },
+    };
+    if let Some(range) = time_range(filters) {
+        filter.insert("timestamp", range);
+    }
+    if let Some(ref model) = filters.model {
+        filter.insert("model", doc! { "$in": model_names(state, model) });
+    }
+    let mut messages: HashMap<ObjectId, ChatMessage> = state
+        .storage()
+        .database()
+        .messages
+        .get_many(filter)
+        .await?
+        .map_ok(|message| (message.id.unwrap(), message))
+        .try_collect()
+        .await?;
+
+    // matches are ordered by similarity, so the first match of a chat is its best
+    let mut ranked: Vec<(ObjectId, Vec<(ChatMessage, f32)>)> = Vec::new();
+    for matched in matches {
+        let Some(message) = messages.remove(&matched.id) else {
+            continue;
+        };
+        match ranked
+            .iter_mut()
+            .find(|(chat_id, _)| *chat_id == message.chat_id)
+        {
+            Some((_, found)) => {
+                if found.len() < MESSAGES_PER_CHAT as usize {
+                    found.push((message, matched.score));
+                }
+            }
+            None => ranked.push((message.chat_id, vec![(message, matched.score)])),
+        }
+    }
+
+    // trashed chats are left out before the limit, so they do not take up results
+    let ids: Vec<ObjectId> = ranked.iter().map(|(id, _)| *id).collect();
+    let mut chats: HashMap<ObjectId, Chat> = state
+        .storage()
+        .database()
+        .chats
+        .get_many(doc! { "_id": { "$in": ids }, "user_id": user_id, "deleted_at": null })
+        .await?
+        .map_ok(|chat| (chat.id.unwrap(), chat))
+        .try_collect()
+        .await?;
+
+    Ok(ranked
+        .into_iter()
+        .filter_map(|(chat_id, messages)| {
+            Some(SemanticMatch {
+                chat: chats.remove(&chat_id)?,
+                score: messages[0].1,
+                messages,
+            })
+        })
+        .take(limit)
+        .collect())
+}
+
+/// Embeds messages sent before semantic search existed, or whose indexing failed.
+pub fn spawn_backfill(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let chats = match state.storage().database().chats.get_many(doc! {}).await {
+            Ok(chats) => chats,
+            Err(e) => {
+                tracing::error!("Failed to list chats to index: {e}");
+                return;
+            }
+        };
+
+        let mut chats = chats.into_stream();
+        let mut indexed = 0;
+        while let Ok(Some(chat)) = chats.try_next().await {
+            let chat_id = chat.id.unwrap();
+            let messages = match unindexed(&state, chat_id).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::error!("Failed to list unindexed messages of chat {chat_id}: {e}");
+                    continue;
+                }
+            };
+            if messages.is_empty() {
+                continue;
+            }
+
+            match index(&state, chat.user_id, &messages).await {
+                Ok(()) => indexed += messages.len(),
+                Err(e) => tracing::error!("Failed to index messages of chat {chat_id}: {e}"),
+            }
+        }
+        if indexed > 0 {
+            tracing::info!("Indexed {indexed} messages.");
+        }
+    });
+}
+
+async fn unindexed(state: &AppState, chat_id: ObjectId) -> anyhow::Result<Vec<ChatMessage>> {
+    Ok(state
+        .storage()
+        .database()
+        .messages
+        .get_many(doc! { "chat_id": chat_id, "indexed": { "$ne": true } })
+        .await?
+        .try_collect()
+        .await?)
+}
@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+/// Characters of message text shown around the first match.
+const SNIPPET_CHARS: usize = 200;
+/// Characters kept before the first match, so it is shown in context.
+const LEADING_CHARS: usize = 60;
+const ELLIPSIS: char = '…';
+
+/// Excerpt of a text with the char ranges of the query terms in it.
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    pub text: String,
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// Lowercased words and quoted phrases of a text search query. Negated terms are left out, since
+/// they never occur in matching text.
+pub fn terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut push = |term: &str| {
+        let term = term.trim().to_lowercase();
+        if !term.is_empty() && !terms.contains(&term) {
+            terms.push(term);
+        }
+    };
+
+    for (i, part) in query.split('"').enumerate() {
+        // odd parts are enclosed in quotes
+        if i % 2 == 1 {
+            push(part);
+            continue;
+        }
+        part.split_whitespace()
+            .filter(|word| !word.starts_with('-'))
+            .for_each(&mut push);
+    }
+
+    terms
+}
+
+/// Sorted, non-overlapping char ranges of the terms in `text`, ignoring case.
+pub fn highlights(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    let text = fold(text);
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    for term in terms {
+        let term = fold(term);
+        if term.is_empty() || term.len() > text.len() {
+            continue;
+        }
+        let mut start = 0;
+        while start + term.len() <= text.len() {
+            if text[start..start + term.len()] == term[..] {
+                ranges.push(start..start + term.len());
+                start += term.len();
+            } else {
+                start += 1;
+            }
+        }
+    }
+
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// Excerpt of `text` around the first occurrence of a term, or its start when none occurs, e.g.
+/// because the search matched a stemmed form of the term.
+pub fn snippet(text: &str, terms: &[String]) -> Snippet {
+    let chars: Vec<char> = text.chars().collect();
+    let ranges = highlights(text, terms);
+
+    let start = ranges
+        .first()
+        .map(|range| range.start.saturating_sub(LEADING_CHARS))
+        .unwrap_or(0);
+    let end = (start + SNIPPET_CHARS).min(chars.len());
+
+    let mut excerpt = String::new();
+    let mut offset = 0;
+    if start > 0 {
+        excerpt.push(ELLIPSIS);
+        offset = 1;
+    }
+    excerpt.extend(&chars[start..end]);
+    if end < chars.len() {
+        excerpt.push(ELLIPSIS);
+    }
+
+    let highlights = ranges
+        .into_iter()
+        .filter(|range| range.start >= start && range.start < end)
+        .map(|range| range.start - start + offset..range.end.min(end) - start + offset)
+        .collect();
+
+    Snippet {
+        text: excerpt,
+        highlights,
+    }
+}
+
+/// Lowercases char by char, so offsets into the result are char offsets into the original.
+fn fold(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
@@ -0,0 +1,300 @@
+pub mod queue;
+pub mod worker;
+
+use futures::TryStreamExt;
+use model::{memory::Memory, message::ChatMessage};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::{
+        auxiliary::{self, AuxiliaryModel, AuxiliaryTask},
+        summary, title,
+    },
+    history,
+    memories::operations,
+    payload::memories::MemoryPayload,
+    state::AppState,
+    streaming::{ApiDelta, ControlChunk},
+};
+
+/// Auxiliary LLM task run in the background by the worker pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Job {
+    /// Names a new chat after its first message.
+    GenerateTitle {
+        chat_id: ObjectId,
+        first_message: String,
+        /// Chat model, used when no auxiliary model is configured.
+        #[serde(default)]
+        model: String,
+    },
+    /// Renames a chat whose recent messages moved to another topic.
+    RetitleChat {
+        chat_id: ObjectId,
+        /// User turn the check was scheduled at.
+        turn: usize,
+        model: String,
+    },
+    /// Updates the user's memories with what a message revealed about them.
+    ExtractMemories {
+        target: operations::Target,
+        assistant_message_id: ObjectId,
+        /// Memories that were in the prompt, in prompt order.
+        memory_ids: Vec<ObjectId>,
+        message: String,
+        #[serde(default)]
+        model: String,
+    },
+    /// Embeds new messages of a chat for semantic search.
+    IndexMessages {
+        user_id: ObjectId,
+        message_ids: Vec<ObjectId>,
+    },
+    /// Folds messages dropped from the context window into the chat's summary.
+    UpdateSummary {
+        chat_id: ObjectId,
+        dropped: Vec<ObjectId>,
+        #[serde(default)]
+        model: String,
+    },
+}
+
+impl Job {
+    /// Jobs with the same key do the same work, so only the first one enqueued runs.
+    pub fn idempotency_key(&self) -> String {
+        match self {
+            Self::GenerateTitle { chat_id, .. } => format!("title:{chat_id}"),
+            Self::RetitleChat { chat_id, turn, .. } => format!("retitle:{chat_id}:{turn}"),
+            Self::ExtractMemories { target, .. } => {
+                format!("memories:{}", target.source_message_id)
+            }
+            Self::IndexMessages { message_ids, .. } => format!(
+                "index:{}",
+                message_ids.last().map(|id| id.to_hex()).unwrap_or_default()
+            ),
+            Self::UpdateSummary {
+                chat_id, dropped, ..
+            } => format!(
+                "summary:{chat_id}:{}",
+                dropped.last().map(|id| id.to_hex()).unwrap_or_default()
+            ),
+        }
+    }
+
+    pub async fn run(
+        self,
+        state: &AppState,
+        listener: Option<&flume::Sender<ApiDelta>>,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::GenerateTitle {
+                chat_id,
+                first_message,
+                model,
+            } => {
+                let Some(chat) = state.storage().database().chats.get_by_id(chat_id).await? else {
+                    return Ok(());
+                };
+                if chat.renamed {
+                    return Ok(());
+                }
+                let Some(auxiliary) =
+                    auxiliary::resolve(state, AuxiliaryTask::Titles, chat.user_id, &model).await?
+                else {
+                    return Ok(());
+                };
+                let name = title::generate(&auxiliary, &first_message).await?;
+                state
+                    .storage()
+                    .database()
+                    .chats
+                    .update(chat_id, doc! { "$set": { "name": &name } })
+                    .await?;
+                notify(listener, ControlChunk::ChatNameUpdated { name }).await;
+            }
+            Self::RetitleChat { chat_id, model, .. } => {
+                let Some(chat) = state.storage().database().chats.get_by_id(chat_id).await? else {
+                    return Ok(());
+                };
+                let Some(current) = chat.name.as_deref().filter(|_| !chat.renamed) else {
+                    return Ok(());
+                };
+                let Some(auxiliary) =
+                    auxiliary::resolve(state, AuxiliaryTask::Titles, chat.user_id, &model).await?
+                else {
+                    return Ok(());
+                };
+
+                let mut recent: Vec<ChatMessage> = state
+                    .storage()
+                    .database()
+                    .messages
+                    .collection()
+                    .find(doc! { "chat_id": chat_id })
+                    .sort(doc! { "timestamp": -1 })
+                    .limit(title::DRIFT_CONTEXT_MESSAGES as i64)
+                    .await?
+                    .try_collect()
+                    .await?;
+                recent.reverse();
+
+                let Some(name) = title::drifted(&auxiliary, current, &recent).await? else {
+                    return Ok(());
+                };
+                state
+                    .storage()
+                    .database()
+                    .chats
+                    .update(chat_id, doc! { "$set": { "name": &name } })
+                    .await?;
+                state.notify_chat(chat_id, ControlChunk::ChatNameUpdated { name });
+            }
+            Self::ExtractMemories {
+                target,
+                assistant_message_id,
+                memory_ids,
+                message,
+                model,
+            } => {
+                let Some(auxiliary) =
+                    auxiliary::resolve(state, AuxiliaryTask::Memories, target.user_id, &model)
+                        .await?
+                else {
+                    return Ok(());
+                };
+                extract_memories(
+                    state,
+                    &auxiliary,
+                    listener,
+                    target,
+                    assistant_message_id,
+                    memory_ids,
+                    &message,
+                )
+                .await?
+            }
+            Self::IndexMessages {
+                user_id,
+                message_ids,
+            } => {
+                let messages: Vec<ChatMessage> = state
+                    .storage()
+                    .database()
+                    .messages
+                    .get_many(doc! { "_id": { "$in": message_ids } })
+                    .await?
+                    .try_collect()
+                    .await?;
+                history::semantic::index(state, user_id, &messages).await?;
+            }
+            Self::UpdateSummary {
+                chat_id,
+                dropped,
+                model,
+            } => {
+                let Some(chat) = state.storage().database().chats.get_by_id(chat_id).await? else {
+                    return Ok(());
+                };
+                let Some(auxiliary) =
+                    auxiliary::resolve(state, AuxiliaryTask::Summaries, chat.user_id, &model)
+                        .await?
+                else {
+                    return Ok(());
+                };
+                let dropped: Vec<ChatMessage> = state
+                    .storage()
+                    .database()
+                    .messages
+                    .get_many_sorted(doc! { "_id": { "$in": dropped } }, doc! { "timestamp": 1 })
+                    .await?
+                    .try_collect()
+                    .await?;
+                summary::update(state, &auxiliary, chat_id, chat.summary, dropped).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn extract_memories(
+    state: &AppState,
+    auxiliary: &AuxiliaryModel,
+    listener: Option<&flume::Sender<ApiDelta>>,
+    target: operations::Target,
+    assistant_message_id: ObjectId,
+    memory_ids: Vec<ObjectId>,
+    message: &str,
+) -> anyhow::Result<()> {
+    let mut existing: Vec<Memory> = state
+        .storage()
+        .database()
+        .memories
+        .get_many(doc! { "_id": { "$in": &memory_ids } })
+        .await?
+        .try_collect()
+        .await?;
+    // memories deleted since the message was sent are left out
+    existing.sort_by_key(|memory| {
+        memory_ids
+            .iter()
+            .position(|id| memory.id == Some(*id))
+            .unwrap_or(usize::MAX)
+    });
+
+    let applied = operations::extract(state, auxiliary, target, &existing, message).await?;
+
+    // the changes are committed, so failures past this point must not retry the extraction
+    let changed: Vec<&str> = applied
+        .added
+        .iter()
+        .chain(&applied.updated)
+        .map(|memory| memory.content.as_str())
+        .collect();
+    if !changed.is_empty() {
+        let result = state
+            .storage()
+            .database()
+            .messages
+            .update(
+                assistant_message_id,
+                doc! { "$set": { "updated_memory": changed.join("\n") } },
+            )
+            .await;
+        if let Err(e) = result {
+            tracing::error!("Failed to record updated memories on the message: {e}");
+        }
+    }
+
+    for memory in applied.added {
+        notify(
+            listener,
+            ControlChunk::MemoryAdded {
+                memory: MemoryPayload::from(memory),
+            },
+        )
+        .await;
+    }
+    for memory in applied.updated {
+        notify(
+            listener,
+            ControlChunk::MemoryUpdated {
+                memory: MemoryPayload::from(memory),
+            },
+        )
+        .await;
+    }
+    for id in applied.removed {
+        notify(listener, ControlChunk::MemoryRemoved { id }).await;
+    }
+
+    Ok(())
+}
+
+async fn notify(listener: Option<&flume::Sender<ApiDelta>>, chunk: ControlChunk) {
+    if let Some(listener) = listener {
+        let _ = listener.send_async(ApiDelta::Control(chunk)).await;
+    }
+}
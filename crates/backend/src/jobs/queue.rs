@@ -0,0 +1,286 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::Utc;
+use redis_om::redis::{self, Script, aio::MultiplexedConnection};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{jobs::Job, streaming::ApiDelta};
+
+/// Jobs waiting to run, scored by the time they become due in milliseconds.
+const PENDING_KEY: &str = "jobs:pending";
+/// Jobs claimed by a worker, scored by the time their lease expires in milliseconds.
+const PROCESSING_KEY: &str = "jobs:processing";
+/// Serialized envelopes by job id.
+const DATA_KEY: &str = "jobs:data";
+/// Envelopes of jobs that failed every attempt, newest first.
+const DEAD_KEY: &str = "jobs:dead";
+const IDEMPOTENCY_KEY_PREFIX: &str = "jobs:key:";
+
+/// Attempts before a job is dead-lettered.
+const MAX_ATTEMPTS: u32 = 5;
+/// Delay before the first retry, doubled for every further attempt.
+const BASE_BACKOFF: Duration = Duration::from_secs(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(15 * 60);
+/// How long a worker may hold a job before it is handed to another worker.
+const LEASE: Duration = Duration::from_secs(5 * 60);
+/// How long an idempotency key prevents the same job from being enqueued again.
+const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long a response stream waits for its job to start, e.g. when another instance claims it.
+const LISTENER_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+/// Dead-lettered jobs kept for inspection.
+const MAX_DEAD_JOBS: isize = 1_000;
+
+/// Moves the first due job into the processing set and returns its envelope.
+const CLAIM_SCRIPT: &str = r#"
+local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1], 'LIMIT', 0, 1)
+if #ids == 0 then
+    return false
+end
+redis.call('ZREM', KEYS[1], ids[1])
+local data = redis.call('HGET', KEYS[3], ids[1])
+if not data then
+    return false
+end
+redis.call('ZADD', KEYS[2], ARGV[2], ids[1])
+return data
+"#;
+
+/// Returns jobs whose lease expired, e.g. because their worker was restarted, to the queue. The
+/// expiry counts as a failed attempt, so a job that keeps stalling its worker is dead-lettered.
+/// The envelope is edited in place, since re-encoding it would turn empty arrays into objects.
+const RECOVER_SCRIPT: &str = r#"
+local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+for _, id in ipairs(ids) do
+    redis.call('ZREM', KEYS[1], id)
+    local data = redis.call('HGET', KEYS[3], id)
+    if data then
+        local attempts = cjson.decode(data).attempts + 1
+        data = string.gsub(data, '"attempts":%d+', '"attempts":' .. attempts, 1)
+        data = string.gsub(data, ',"last_error":.*}$', ',"last_error":"Lease expired"}', 1)
+        if attempts >= tonumber(ARGV[2]) then
+            redis.call('LPUSH', KEYS[4], data)
+            redis.call('LTRIM', KEYS[4], 0, tonumber(ARGV[3]) - 1)
+            redis.call('HDEL', KEYS[3], id)
+        else
+            redis.call('HSET', KEYS[3], id, data)
+            redis.call('ZADD', KEYS[2], ARGV[1], id)
+        end
+    end
+end
+return #ids
+"#;
+
+/// A job with its delivery state, as stored in Redis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEnvelope {
+    pub id: String,
+    pub job: Job,
+    /// Attempts that already failed.
+    pub attempts: u32,
+    pub enqueued_at: chrono::DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+/// Durable queue of background jobs, shared by every backend instance through Redis.
+pub struct JobQueue {
+    connection: MultiplexedConnection,
+    /// Response streams waiting for the outcome of a job enqueued by this instance.
+    listeners: Arc<Mutex<HashMap<String, flume::Sender<ApiDelta>>>>,
+}
+
+impl JobQueue {
+    pub fn new(connection: MultiplexedConnection) -> Self {
+        Self {
+            connection,
+            listeners: Default::default(),
+        }
+    }
+
+    /// Enqueues `job` unless a job with the same idempotency key was enqueued recently.
+    /// `listener` receives the control chunks of the job's first attempt, if it runs on this
+    /// instance. Returns the id of the new job.
+    pub async fn enqueue(
+        &self,
+        job: Job,
+        listener: Option<flume::Sender<ApiDelta>>,
+    ) -> anyhow::Result<Option<String>> {
+        let mut connection = self.connection.clone();
+        let envelope = JobEnvelope {
+            id: Uuid::new_v4().to_string(),
+            job,
+            attempts: 0,
+            enqueued_at: Utc::now(),
+            last_error: None,
+        };
+
+        let idempotency_key = format!("{IDEMPOTENCY_KEY_PREFIX}{}", envelope.job.idempotency_key());
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(&idempotency_key)
+            .arg(&envelope.id)
+            .arg("NX")
+            .arg("EX")
+            .arg(IDEMPOTENCY_TTL.as_secs())
+            .query_async(&mut connection)
+            .await?;
+        if claimed.is_none() {
+            tracing::debug!("Skipped duplicate job {}.", envelope.job.idempotency_key());
+            return Ok(None);
+        }
+
+        // registered before the job is visible, so a worker cannot claim it unnoticed
+        if let Some(listener) = listener {
+            self.listeners
+                .lock()
+                .unwrap()
+                .insert(envelope.id.clone(), listener);
+            let listeners = Arc::clone(&self.listeners);
+            let id = envelope.id.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(LISTENER_TIMEOUT).await;
+                listeners.lock().unwrap().remove(&id);
+            });
+        }
+        let result = redis::pipe()
+            .atomic()
+            .hset(DATA_KEY, &envelope.id, serde_json::to_string(&envelope)?)
+            .zadd(PENDING_KEY, &envelope.id, now_millis())
+            .query_async::<_, ()>(&mut connection)
+            .await;
+        if let Err(e) = result {
+            self.take_listener(&envelope.id);
+            // the job was never queued, so the same work may be enqueued again
+            let _ = redis::cmd("DEL")
+                .arg(&idempotency_key)
+                .query_async::<_, ()>(&mut connection)
+                .await;
+            return Err(e.into());
+        }
+
+        Ok(Some(envelope.id))
+    }
+
+    /// Leases the next due job to the caller.
+    pub async fn claim(&self) -> anyhow::Result<Option<JobEnvelope>> {
+        let mut connection = self.connection.clone();
+        let now = now_millis();
+        let data: Option<String> = Script::new(CLAIM_SCRIPT)
+            .key(PENDING_KEY)
+            .key(PROCESSING_KEY)
+            .key(DATA_KEY)
+            .arg(now)
+            .arg(now + LEASE.as_millis() as i64)
+            .invoke_async(&mut connection)
+            .await?;
+
+        data.map(|data| serde_json::from_str(&data).map_err(Into::into))
+            .transpose()
+    }
+
+    /// Listener registered for the job, removed so only the first attempt notifies it and
+    /// retries never hold a response stream open.
+    pub fn take_listener(&self, id: &str) -> Option<flume::Sender<ApiDelta>> {
+        self.listeners.lock().unwrap().remove(id)
+    }
+
+    pub async fn complete(&self, envelope: &JobEnvelope) -> anyhow::Result<()> {
+        let mut connection = self.connection.clone();
+        redis::pipe()
+            .atomic()
+            .zrem(PROCESSING_KEY, &envelope.id)
+            .hdel(DATA_KEY, &envelope.id)
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Schedules a retry with exponential backoff, or dead-letters the job once it failed
+    /// every attempt.
+    pub async fn fail(
+        &self,
+        mut envelope: JobEnvelope,
+        error: &anyhow::Error,
+    ) -> anyhow::Result<()> {
+        let mut connection = self.connection.clone();
+        envelope.attempts += 1;
+        envelope.last_error = Some(format!("{error:#}"));
+        let data = serde_json::to_string(&envelope)?;
+
+        if envelope.attempts >= MAX_ATTEMPTS {
+            tracing::error!(
+                "Job {} failed {} times and was dead-lettered: {error:#}",
+                envelope.id,
+                envelope.attempts
+            );
+            redis::pipe()
+                .atomic()
+                .lpush(DEAD_KEY, data)
+                .ltrim(DEAD_KEY, 0, MAX_DEAD_JOBS - 1)
+                .zrem(PROCESSING_KEY, &envelope.id)
+                .hdel(DATA_KEY, &envelope.id)
+                .query_async::<_, ()>(&mut connection)
+                .await?;
+            return Ok(());
+        }
+
+        let backoff = BASE_BACKOFF
+            .saturating_mul(2u32.saturating_pow(envelope.attempts - 1))
+            .min(MAX_BACKOFF);
+        tracing::warn!(
+            "Job {} failed, retrying in {}s: {error:#}",
+            envelope.id,
+            backoff.as_secs()
+        );
+        redis::pipe()
+            .atomic()
+            .hset(DATA_KEY, &envelope.id, data)
+            .zrem(PROCESSING_KEY, &envelope.id)
+            .zadd(
+                PENDING_KEY,
+                &envelope.id,
+                now_millis() + backoff.as_millis() as i64,
+            )
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Extends the lease of a job that is still running, unless it already expired.
+    pub async fn extend_lease(&self, id: &str) -> anyhow::Result<()> {
+        let mut connection = self.connection.clone();
+        redis::cmd("ZADD")
+            .arg(PROCESSING_KEY)
+            .arg("XX")
+            .arg(now_millis() + LEASE.as_millis() as i64)
+            .arg(id)
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Requeues or dead-letters jobs whose lease expired. Returns how many were recovered.
+    pub async fn recover_expired(&self) -> anyhow::Result<u64> {
+        let mut connection = self.connection.clone();
+        Ok(Script::new(RECOVER_SCRIPT)
+            .key(PROCESSING_KEY)
+            .key(PENDING_KEY)
+            .key(DATA_KEY)
+            .key(DEAD_KEY)
+            .arg(now_millis())
+            .arg(MAX_ATTEMPTS)
+            .arg(MAX_DEAD_JOBS)
+            .invoke_async(&mut connection)
+            .await?)
+    }
+}
+
+fn now_millis() -> i64 {
+    Utc::now().timestamp_millis()
+}
@@ -0,0 +1,75 @@
+use std::{env, sync::Arc, time::Duration};
+
+use crate::{jobs::queue::JobEnvelope, state::AppState};
+
+const DEFAULT_WORKERS: usize = 4;
+/// Wait between polls of an empty queue.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Wait after Redis could not be reached.
+const ERROR_DELAY: Duration = Duration::from_secs(5);
+const RECOVERY_INTERVAL: Duration = Duration::from_secs(60);
+/// How often a running job's lease is extended, well within the lease itself.
+const LEASE_RENEWAL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Starts `JOB_WORKERS` workers running queued jobs, and requeues jobs abandoned by workers
+/// that stopped mid-job.
+pub fn spawn(state: Arc<AppState>) {
+    let workers = env::var("JOB_WORKERS")
+        .ok()
+        .and_then(|workers| workers.parse().ok())
+        .unwrap_or(DEFAULT_WORKERS);
+
+    for _ in 0..workers {
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            loop {
+                match state.jobs().claim().await {
+                    Ok(Some(envelope)) => process(&state, envelope).await,
+                    Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+                    Err(e) => {
+                        tracing::error!("Failed to claim a job: {e}");
+                        tokio::time::sleep(ERROR_DELAY).await;
+                    }
+                }
+            }
+        });
+    }
+
+    tokio::spawn(async move {
+        loop {
+            match state.jobs().recover_expired().await {
+                Ok(0) => {}
+                Ok(recovered) => tracing::warn!("Recovered {recovered} abandoned jobs."),
+                Err(e) => tracing::error!("Failed to recover abandoned jobs: {e}"),
+            }
+            tokio::time::sleep(RECOVERY_INTERVAL).await;
+        }
+    });
+}
+
+async fn process(state: &AppState, envelope: JobEnvelope) {
+    let listener = state.jobs().take_listener(&envelope.id);
+    let run = envelope.job.clone().run(state, listener.as_ref());
+    tokio::pin!(run);
+    let mut renewal = tokio::time::interval(LEASE_RENEWAL_INTERVAL);
+    // the first tick completes immediately, right after the job was claimed
+    renewal.tick().await;
+    let outcome = loop {
+        tokio::select! {
+            outcome = &mut run => break outcome,
+            _ = renewal.tick() => {
+                if let Err(e) = state.jobs().extend_lease(&envelope.id).await {
+                    tracing::warn!("Failed to extend the lease of job {}: {e}", envelope.id);
+                }
+            }
+        }
+    };
+
+    let result = match outcome {
+        Ok(()) => state.jobs().complete(&envelope).await,
+        Err(e) => state.jobs().fail(envelope.clone(), &e).await,
+    };
+    if let Err(e) = result {
+        tracing::error!("Failed to update job {}: {e}", envelope.id);
+    }
+}
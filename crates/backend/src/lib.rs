@@ -1,9 +1,17 @@
+pub mod context;
 pub mod data;
+pub mod documents;
 pub mod errors;
+pub mod history;
+pub mod jobs;
 pub mod logger;
+pub mod memories;
 pub mod middleware;
 pub mod models;
 pub mod payload;
+pub mod retention;
 pub mod routes;
 pub mod state;
 pub mod streaming;
+pub mod temporary;
+pub mod trash;
@@ -19,6 +19,6 @@ impl Logger {
 
         subscriber.init();
 
-        return Ok(Self());
+        Ok(Self())
     }
 }
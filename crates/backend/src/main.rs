@@ -2,7 +2,10 @@ use std::{process, sync::Arc};
 
 use axum::Router;
 
-use backend::{logger::Logger, middleware::auth::AuthMiddlewareLayer, routes, state::AppState};
+use backend::{
+    history, jobs, logger::Logger, memories, middleware::auth::AuthMiddlewareLayer, models,
+    retention, routes, state::AppState, trash,
+};
 use tower_http::cors::CorsLayer;
 
 #[tokio::main]
@@ -17,6 +20,14 @@ async fn main() {
     let app_state = AppState::new().await.unwrap();
     let app_state = Arc::new(app_state);
 
+    models::reload::spawn(Arc::clone(&app_state));
+    models::sync::spawn(Arc::clone(&app_state));
+    memories::spawn_backfill(Arc::clone(&app_state));
+    history::semantic::spawn_backfill(Arc::clone(&app_state));
+    jobs::worker::spawn(Arc::clone(&app_state));
+    trash::spawn_purge(Arc::clone(&app_state)).unwrap();
+    retention::spawn_sweep(Arc::clone(&app_state)).unwrap();
+
     let app = Router::new()
         .merge(routes::router())
         .with_state(Arc::clone(&app_state))
@@ -0,0 +1,174 @@
+pub mod operations;
+
+use std::{collections::HashMap, sync::Arc};
+
+use futures::TryStreamExt;
+use model::{
+    memory::Memory,
+    project::Project,
+    vector::{VectorNamespace, VectorRecord},
+};
+use mongodb::bson::{doc, oid::ObjectId};
+
+use crate::{
+    context::{project, tokens::TokenEstimator},
+    state::{AppState, storage::vectors::VectorQuery},
+};
+
+/// Most unpinned memories added to a single turn.
+const TOP_K: usize = 10;
+/// Vector matches fetched before filtering by project scope, so scoped memories of other
+/// projects do not crowd out the ones that apply.
+const CANDIDATES: usize = 40;
+/// Memories less similar than this rarely help with the message.
+const MIN_SCORE: f32 = 0.2;
+/// Upper bound on prompt tokens spent on memories.
+const MAX_MEMORY_TOKENS: u32 = 1_000;
+
+/// Embeds a memory so it can be retrieved by similarity.
+pub async fn index(state: &AppState, memory: &Memory) -> anyhow::Result<()> {
+    let id = memory
+        .id
+        .ok_or_else(|| anyhow::anyhow!("Memory has no id"))?;
+    let vector = state
+        .inference()
+        .embed(vec![memory.content.clone()])
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("Embeddings API returned no vector"))?;
+
+    state
+        .storage()
+        .vectors()
+        .upsert(vec![VectorRecord {
+            id,
+            namespace: VectorNamespace::Memories,
+            user_id: memory.user_id,
+            group: memory.project_id,
+            vector,
+        }])
+        .await?;
+    state
+        .storage()
+        .database()
+        .memories
+        .update(id, doc! { "$set": { "indexed": true } })
+        .await
+}
+
+pub async fn remove(state: &AppState, memory_id: ObjectId) -> anyhow::Result<()> {
+    state
+        .storage()
+        .vectors()
+        .delete(VectorNamespace::Memories, &[memory_id])
+        .await
+}
+
+/// Pinned memories visible in the chat, followed by the other memories most relevant to
+/// `query`. Pinned memories are always included; the rest fill what is left of the budget.
+pub async fn relevant(
+    state: &AppState,
+    user_id: ObjectId,
+    project: Option<&Project>,
+    query: &str,
+    estimator: &TokenEstimator,
+) -> anyhow::Result<Vec<Memory>> {
+    let mut filter = project::memory_filter(user_id, project);
+    filter.insert("pinned", true);
+    let mut memories: Vec<Memory> = state
+        .storage()
+        .database()
+        .memories
+        .get_many_sorted(filter, doc! { "_id": 1 })
+        .await?
+        .try_collect()
+        .await?;
+    if query.trim().is_empty() {
+        return Ok(memories);
+    }
+
+    let vector = state
+        .inference()
+        .embed(vec![query.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("Embeddings API returned no vector"))?;
+    let matches = state
+        .storage()
+        .vectors()
+        .query(VectorQuery {
+            namespace: VectorNamespace::Memories,
+            vector,
+            user_id: Some(user_id),
+            groups: None,
+            limit: CANDIDATES,
+            min_score: MIN_SCORE,
+        })
+        .await?;
+    if matches.is_empty() {
+        return Ok(memories);
+    }
+
+    let mut filter = project::memory_filter(user_id, project);
+    filter.insert("pinned", doc! { "$ne": true });
+    filter.insert(
+        "_id",
+        doc! { "$in": matches.iter().map(|matched| matched.id).collect::<Vec<_>>() },
+    );
+    let mut candidates: HashMap<ObjectId, Memory> = state
+        .storage()
+        .database()
+        .memories
+        .get_many(filter)
+        .await?
+        .map_ok(|memory| (memory.id.unwrap(), memory))
+        .try_collect()
+        .await?;
+
+    let mut used: u32 = memories
+        .iter()
+        .map(|memory| estimator.text(&memory.content))
+        .sum();
+    memories.extend(
+        matches
+            .into_iter()
+            .filter_map(|matched| candidates.remove(&matched.id))
+            .take(TOP_K)
+            .take_while(|memory| {
+                used += estimator.text(&memory.content);
+                used <= MAX_MEMORY_TOKENS
+            }),
+    );
+    Ok(memories)
+}
+
+/// Embeds memories created before retrieval existed, or whose indexing failed.
+pub fn spawn_backfill(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let memories = match state
+            .storage()
+            .database()
+            .memories
+            .get_many(doc! { "indexed": { "$ne": true } })
+            .await
+        {
+            Ok(memories) => memories,
+            Err(e) => {
+                tracing::error!("Failed to list unindexed memories: {e}");
+                return;
+            }
+        };
+
+        let mut memories = memories.into_stream();
+        let mut indexed = 0;
+        while let Ok(Some(memory)) = memories.try_next().await {
+            match index(&state, &memory).await {
+                Ok(()) => indexed += 1,
+                Err(e) => tracing::error!("Failed to index memory {:?}: {e}", memory.id),
+            }
+        }
+        if indexed > 0 {
+            tracing::info!("Indexed {indexed} memories.");
+        }
+    });
+}
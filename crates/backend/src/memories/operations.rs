@@ -0,0 +1,404 @@
+use std::collections::HashSet;
+
+use ai::openai::structured::StructuredRequest;
+use chrono::Utc;
+use model::memory::Memory;
+use mongodb::{
+    ClientSession,
+    bson::{self, doc, oid::ObjectId},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{context::auxiliary::AuxiliaryModel, data::transaction, memories, state::AppState};
+
+/// Most operations accepted from a single extraction.
+const MAX_OPERATIONS: usize = 5;
+/// Longest memory the extractor may write, matching the limit of the memory API.
+const MAX_CONTENT_CHARS: usize = 1_000;
+
+/// Change to the user's memories proposed by the extractor. Existing memories are referenced by
+/// their position in the prompt, since models copy short numbers more reliably than object ids.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Operation {
+    Add { content: String },
+    Update { id: usize, content: String },
+    Delete { id: usize },
+    None,
+}
+
+#[derive(Debug, Deserialize)]
+struct Operations {
+    operations: Vec<Operation>,
+}
+
+/// Validated operation, referencing memories by id.
+#[derive(Debug)]
+enum Change {
+    Add(String),
+    Update(ObjectId, String),
+    Delete(ObjectId),
+}
+
+/// Where memories written by an extraction belong.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Target {
+    pub user_id: ObjectId,
+    /// Project new memories are scoped to.
+    pub project_id: Option<ObjectId>,
+    /// User message the memories are extracted from.
+    pub source_message_id: ObjectId,
+}
+
+/// Memories changed by an extraction, in their state after it was applied.
+#[derive(Debug, Default)]
+pub struct Applied {
+    pub added: Vec<Memory>,
+    pub updated: Vec<Memory>,
+    pub removed: Vec<ObjectId>,
+}
+
+#[derive(Debug, Serialize)]
+struct PromptMemory<'a> {
+    id: usize,
+    content: &'a str,
+}
+
+/// Asks the auxiliary model how `message` changes the user's memories and applies its answer.
+/// `existing` are the memories shown to the model; only unpinned ones may be changed.
+pub async fn extract(
+    state: &AppState,
+    auxiliary: &AuxiliaryModel,
+    target: Target,
+    existing: &[Memory],
+    message: &str,
+) -> anyhow::Result<Applied> {
+    let request = StructuredRequest {
+        temperature: Some(0.3),
+        max_tokens: Some(1000),
+        ..StructuredRequest::new("memory_operations", schema(), prompt(existing, message))
+    };
+    let changes = auxiliary
+        .client
+        .clone()
+        .structured_completion(auxiliary.model.clone(), request, |operations| {
+            validate(operations, existing)
+        })
+        .await?;
+    if changes.is_empty() {
+        return Ok(Applied::default());
+    }
+    apply(state, target, existing, changes).await
+}
+
+fn schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "operations": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "op": { "type": "string", "enum": ["add", "update", "delete", "none"] },
+                        "id": { "type": ["integer", "null"] },
+                        "content": { "type": ["string", "null"] },
+                    },
+                    "required": ["op", "id", "content"],
+                    "additionalProperties": false,
+                },
+            },
+        },
+        "required": ["operations"],
+        "additionalProperties": false,
+    })
+}
+
+fn prompt(existing: &[Memory], message: &str) -> String {
+    let existing: Vec<PromptMemory> = existing
+        .iter()
+        .enumerate()
+        .filter(|(_, memory)| !memory.pinned)
+        .map(|(index, memory)| PromptMemory {
+            id: index + 1,
+            content: &memory.content,
+        })
+        .collect();
+
+    format!(
+        r#"You are an AI Memory Assistant. You keep a list of short facts about the user up to date.
+Analyze the current user message and decide how it changes the existing memories:
+- "add": the message contains important new information (e.g., goals, preferences, or facts) that no existing memory covers.
+- "update": the message refines or contradicts an existing memory; rewrite that memory so it is correct.
+- "delete": the message makes an existing memory obsolete or the user asks to forget it.
+- "none": nothing worth remembering changed.
+Never add a memory that duplicates an existing one; update it instead. Each memory must be a single concise sentence in third person, e.g. "User is building an AI chat.".
+Output at most {MAX_OPERATIONS} operations, e.g.:
+{{"operations": [{{"op": "add", "id": null, "content": "..."}}, {{"op": "update", "id": 1, "content": "..."}}, {{"op": "delete", "id": 2, "content": null}}]}}
+Output {{"operations": [{{"op": "none", "id": null, "content": null}}]}} if nothing changed.
+
+Existing memories: {}
+
+Current user message: {:?}"#,
+        if existing.is_empty() {
+            "No memories yet.".to_string()
+        } else {
+            serde_json::to_string(&existing).unwrap()
+        },
+        message.trim()
+    )
+}
+
+/// Validates the extractor's output. The output is rejected as a whole if any operation is
+/// invalid, so a misunderstood prompt never partially rewrites the user's memories; the model
+/// is then asked to correct it.
+fn validate(
+    Operations { operations }: Operations,
+    existing: &[Memory],
+) -> anyhow::Result<Vec<Change>> {
+    let operations: Vec<Operation> = operations
+        .into_iter()
+        .filter(|operation| !matches!(operation, Operation::None))
+        .collect();
+    if operations.len() > MAX_OPERATIONS {
+        anyhow::bail!("Too many memory operations: {}", operations.len());
+    }
+
+    let mut touched = HashSet::new();
+    let mut target = |id: usize| -> anyhow::Result<&Memory> {
+        let memory = id
+            .checked_sub(1)
+            .and_then(|index| existing.get(index))
+            .filter(|memory| !memory.pinned)
+            .ok_or_else(|| anyhow::anyhow!("Unknown memory {id}"))?;
+        if !touched.insert(id) {
+            anyhow::bail!("Memory {id} is changed more than once");
+        }
+        Ok(memory)
+    };
+    let content = |content: String| -> anyhow::Result<String> {
+        let content = content.trim().to_string();
+        if content.is_empty() || content.chars().count() > MAX_CONTENT_CHARS {
+            anyhow::bail!("Memory content must be 1-{MAX_CONTENT_CHARS} chars long");
+        }
+        Ok(content)
+    };
+
+    let mut changes = Vec::with_capacity(operations.len());
+    for operation in operations {
+        match operation {
+            Operation::Add { content: new } => changes.push(Change::Add(content(new)?)),
+            Operation::Update { id, content: new } => {
+                let memory = target(id)?;
+                let new = content(new)?;
+                if new != memory.content {
+                    changes.push(Change::Update(memory.id.unwrap(), new));
+                }
+            }
+            Operation::Delete { id } => changes.push(Change::Delete(target(id)?.id.unwrap())),
+            Operation::None => {}
+        }
+    }
+    Ok(changes)
+}
+
+/// Write already done to the database, and how to revert it when transactions are unavailable.
+enum Undo {
+    Created(ObjectId),
+    /// Memory as it was before, and the content it was updated to.
+    Updated(Memory, String),
+    Deleted(Memory),
+}
+
+/// Applies all changes or none of them, in a transaction when the server supports them.
+async fn apply(
+    state: &AppState,
+    target: Target,
+    existing: &[Memory],
+    changes: Vec<Change>,
+) -> anyhow::Result<Applied> {
+    let mut applied = match apply_in_transaction(state, target, existing, &changes).await {
+        Err(e) if transaction::unsupported(&e) => {
+            apply_with_undo(state, target, existing, &changes).await?
+        }
+        result => result?,
+    };
+
+    // embeddings are refreshed once the changes are committed; failures are left to the backfill
+    for memory in applied.added.iter_mut().chain(applied.updated.iter_mut()) {
+        match memories::index(state, memory).await {
+            Ok(()) => memory.indexed = true,
+            Err(e) => tracing::error!("Failed to index memory {:?}: {e}", memory.id),
+        }
+    }
+    for &id in &applied.removed {
+        if let Err(e) = memories::remove(state, id).await {
+            tracing::error!("Failed to remove the vector of memory {id}: {e}");
+        }
+    }
+
+    Ok(applied)
+}
+
+async fn apply_in_transaction(
+    state: &AppState,
+    target: Target,
+    existing: &[Memory],
+    changes: &[Change],
+) -> mongodb::error::Result<Applied> {
+    let mut session = state.storage().database().client().start_session().await?;
+    session.start_transaction().await?;
+
+    let mut applied = Applied::default();
+    for change in changes {
+        if let Err(e) = apply_one(
+            state,
+            target,
+            existing,
+            change,
+            &mut applied,
+            Some(&mut session),
+        )
+        .await
+        {
+            let _ = session.abort_transaction().await;
+            return Err(e);
+        }
+    }
+    session.commit_transaction().await?;
+
+    Ok(applied)
+}
+
+/// Fallback for standalone servers: writes are reverted in reverse order when one fails.
+async fn apply_with_undo(
+    state: &AppState,
+    target: Target,
+    existing: &[Memory],
+    changes: &[Change],
+) -> mongodb::error::Result<Applied> {
+    let mut applied = Applied::default();
+    let mut undo = Vec::with_capacity(changes.len());
+
+    for change in changes {
+        match apply_one(state, target, existing, change, &mut applied, None).await {
+            Ok(done) => undo.push(done),
+            Err(e) => {
+                revert(state, undo).await;
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(applied)
+}
+
+async fn apply_one(
+    state: &AppState,
+    target: Target,
+    existing: &[Memory],
+    change: &Change,
+    applied: &mut Applied,
+    session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<Undo> {
+    let collection = state.storage().database().memories.collection();
+    let now = Utc::now();
+    let previous = |id: ObjectId| {
+        existing
+            .iter()
+            .find(|memory| memory.id == Some(id))
+            .cloned()
+            .unwrap()
+    };
+
+    match *change {
+        Change::Add(ref content) => {
+            let memory = Memory {
+                id: Some(ObjectId::new()),
+                user_id: target.user_id,
+                content: content.clone(),
+                project_id: target.project_id,
+                indexed: false,
+                tags: vec![],
+                pinned: false,
+                source_message_id: Some(target.source_message_id),
+                created_at: Some(now),
+                updated_at: Some(now),
+            };
+            let action = collection.insert_one(&memory);
+            match session {
+                Some(session) => action.session(session).await?,
+                None => action.await?,
+            };
+            let id = memory.id.unwrap();
+            applied.added.push(memory);
+            Ok(Undo::Created(id))
+        }
+        Change::Update(id, ref content) => {
+            let previous = previous(id);
+            let action = collection.update_one(
+                doc! { "_id": id },
+                doc! {
+                    "$set": {
+                        "content": content,
+                        "source_message_id": target.source_message_id,
+                        "updated_at": bson::DateTime::from_chrono(now),
+                        "indexed": false,
+                    }
+                },
+            );
+            match session {
+                Some(session) => action.session(session).await?,
+                None => action.await?,
+            };
+            applied.updated.push(Memory {
+                content: content.clone(),
+                source_message_id: Some(target.source_message_id),
+                updated_at: Some(now),
+                indexed: false,
+                ..previous.clone()
+            });
+            Ok(Undo::Updated(previous, content.clone()))
+        }
+        Change::Delete(id) => {
+            let previous = previous(id);
+            let action = collection.delete_one(doc! { "_id": id });
+            match session {
+                Some(session) => action.session(session).await?,
+                None => action.await?,
+            };
+            applied.removed.push(id);
+            Ok(Undo::Deleted(previous))
+        }
+    }
+}
+
+async fn revert(state: &AppState, undo: Vec<Undo>) {
+    let collection = state.storage().database().memories.collection();
+    for undo in undo.into_iter().rev() {
+        let result = match undo {
+            Undo::Created(id) => collection.delete_one(doc! { "_id": id }).await.map(|_| ()),
+            // only the fields the update wrote are restored, and only while nobody edited the
+            // memory since
+            Undo::Updated(memory, content) => collection
+                .update_one(
+                    doc! { "_id": memory.id.unwrap(), "content": content },
+                    doc! {
+                        "$set": {
+                            "content": &memory.content,
+                            "source_message_id": memory.source_message_id,
+                            "updated_at": memory.updated_at.map(bson::DateTime::from_chrono),
+                            "indexed": memory.indexed,
+                        }
+                    },
+                )
+                .await
+                .map(|_| ()),
+            Undo::Deleted(memory) => collection.insert_one(memory).await.map(|_| ()),
+        };
+        if let Err(e) = result {
+            tracing::error!("Failed to revert memory change: {e}");
+        }
+    }
+}
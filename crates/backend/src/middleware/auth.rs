@@ -100,7 +100,7 @@ where
         let mut conn = self.state.storage().cache().connection();
         Box::pin(async move {
             if let Some(session_id) = session_id {
-                let session = SessionModel::get(&session_id.to_string(), &mut conn).await;
+                let session = SessionModel::get(session_id.to_string(), &mut conn).await;
                 if let Ok(session) = session {
                     request.extensions_mut().insert::<SessionModel>(session);
                 }
@@ -1,160 +1,203 @@
-use serde::Serialize;
+pub mod reload;
+pub mod sync;
+
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::Path,
+};
+
+use ai::openai::models::OpenAIModel;
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
 
 use crate::state::inference::InferenceProvider;
 
+pub const DEFAULT_MODELS_CONFIG_PATH: &str = "models.toml";
+
+#[derive(Clone)]
 pub struct ModelsConfig {
+    catalog: Vec<Model>,
+    sync: SyncConfig,
     free_models: Vec<Model>,
     paid_models: Vec<Model>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ModelsFile {
+    models: Vec<Model>,
+    #[serde(default)]
+    sync: SyncConfig,
+}
+
+/// Controls the background job that reconciles the catalog with the provider's model list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncConfig {
+    #[serde(default = "SyncConfig::default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "SyncConfig::default_interval_secs")]
+    pub interval_secs: u64,
+    /// Discovered models that are not in the catalog, but should be exposed anyway.
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+impl SyncConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+
+    fn default_interval_secs() -> u64 {
+        3600
+    }
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            interval_secs: Self::default_interval_secs(),
+            allowlist: Vec::new(),
+        }
+    }
+}
+
 impl ModelsConfig {
-    pub fn new() -> Self {
+    pub fn config_path() -> String {
+        env::var("MODELS_CONFIG_PATH").unwrap_or_else(|_| DEFAULT_MODELS_CONFIG_PATH.to_string())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read model catalog at {}", path.display()))?;
+
+        Self::parse(&contents)
+            .with_context(|| format!("Invalid model catalog at {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        let file: ModelsFile = toml::from_str(contents)?;
+        Self::validate(&file.models)?;
+        if file.sync.interval_secs == 0 {
+            bail!("Model sync interval must be positive");
+        }
+
+        Ok(Self::from_models(
+            file.models.clone(),
+            file.models,
+            file.sync,
+        ))
+    }
+
+    fn from_models(catalog: Vec<Model>, models: Vec<Model>, sync: SyncConfig) -> Self {
         Self {
-            free_models: vec![
-                Model {
-                    identifier: "google/gemini-2.0-flash-exp:free".to_string(),
-                    name: "Gemini 2.0 Flash Experimental".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: false,
-                    author: "Google".to_string(),
-                },
-                Model {
-                    identifier: "meta-llama/llama-4-maverick:free".to_string(),
-                    name: "Llama 4 Maverick".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: false,
-                    author: "Meta".to_string(),
-                },
-                Model {
-                    identifier: "deepseek/deepseek-r1-distill-llama-70b:free".to_string(),
-                    name: "DeepSeek R1 Distill Llama 70B".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "DeepSeek".to_string(),
-                },
-                Model {
-                    identifier: "meta-llama/llama-4-scout:free".to_string(),
-                    name: "Llama 4 Scout".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: false,
-                    author: "Meta".to_string(),
-                },
-                Model {
-                    identifier: "nvidia/llama-3.1-nemotron-ultra-253b-v1:free".to_string(),
-                    name: "Llama 3.1 Nemotron Ultra".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "NVIDIA".to_string(),
-                },
-                Model {
-                    identifier: "google/gemma-3-27b-it:free".to_string(),
-                    name: "Gemma 3".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: false,
-                    author: "Google".to_string(),
-                },
-                Model {
-                    identifier: "deepseek/deepseek-chat-v3-0324:free".to_string(),
-                    name: "DeepSeek V3".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: false,
-                    author: "DeepSeek".to_string(),
-                },
-                Model {
-                    identifier: "deepseek/deepseek-r1-0528:free".to_string(),
-                    name: "DeepSeek R1".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "DeepSeek".to_string(),
-                },
-                Model {
-                    identifier: "tngtech/deepseek-r1t-chimera:free".to_string(),
-                    name: "DeepSeek R1T Chimera".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "TNG".to_string(),
-                },
-                Model {
-                    identifier: "qwen/qwen3-235b-a22b:free".to_string(),
-                    name: "Qwen 235B A22B".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Qwen".to_string(),
-                },
-                Model {
-                    identifier: "qwen/qwq-32b:free".to_string(),
-                    name: "QWQ 32B".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Qwen".to_string(),
-                },
-            ],
-            paid_models: vec![
-                Model {
-                    identifier: "anthropic/claude-sonnet-4".to_string(),
-                    name: "Claude Sonnet 4".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Anthropic".to_string(),
-                },
-                Model {
-                    identifier: "anthropic/claude-opus-4".to_string(),
-                    name: "Claude Opus 4".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Anthropic".to_string(),
-                },
-                Model {
-                    identifier: "google/gemini-2.5-pro-preview".to_string(),
-                    name: "Gemini 2.5 Pro Preview".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Google".to_string(),
-                },
-                Model {
-                    identifier: "openai/gpt-4o-mini".to_string(),
-                    name: "GPT-4o-mini".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "OpenAI".to_string(),
-                },
-                Model {
-                    identifier: "google/gemini-2.5-flash-preview".to_string(),
-                    name: "Gemini 2.5 Flash Preview".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Google".to_string(),
-                },
-                Model {
-                    identifier: "google/gemini-2.5-flash-preview-05-20:thinking".to_string(),
-                    name: "Gemini 2.5 Flash Preview (thinking)".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Google".to_string(),
-                },
-                Model {
-                    identifier: "meta-llama/llama-3.1-70b-instruct".to_string(),
-                    name: "Llama 3.1 70B Instruct".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Meta".to_string(),
-                },
-                Model {
-                    identifier: "perplexity/llama-3.1-sonar-large-128k-online".to_string(),
-                    name: "Llama 3.1 Sonar 70B Online".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "Perplexity".to_string(),
-                },
-                Model {
-                    identifier: "openai/gpt-4-turbo".to_string(),
-                    name: "GPT-4 Turbo".to_string(),
-                    provider: InferenceProvider::OpenRouter,
-                    is_reasoning: true,
-                    author: "OpenAI".to_string(),
-                },
-            ],
+            catalog,
+            sync,
+            free_models: models
+                .iter()
+                .filter(|model| model.tiers.contains(&ModelTier::Free))
+                .cloned()
+                .collect(),
+            paid_models: models
+                .into_iter()
+                .filter(|model| model.tiers.contains(&ModelTier::Paid))
+                .collect(),
+        }
+    }
+
+    fn validate(models: &[Model]) -> anyhow::Result<()> {
+        if models.is_empty() {
+            bail!("Model catalog is empty");
+        }
+
+        let mut identifiers = HashSet::new();
+        for model in models {
+            if model.identifier.trim().is_empty() {
+                bail!("Model identifier must not be empty");
+            }
+            if !identifiers.insert(model.identifier.as_str()) {
+                bail!("Duplicate model identifier: {}", model.identifier);
+            }
+            if model.name.trim().is_empty() {
+                bail!("Model {} has an empty display name", model.identifier);
+            }
+            if model.tiers.is_empty() {
+                bail!("Model {} is not assigned to any tier", model.identifier);
+            }
+            if model.context_length == 0 {
+                bail!("Model {} has a zero context length", model.identifier);
+            }
+            if model.pricing.prompt < 0. || model.pricing.completion < 0. {
+                bail!("Model {} has negative pricing", model.identifier);
+            }
+            if model
+                .capabilities
+                .max_output
+                .is_some_and(|max_output| max_output == 0 || max_output > model.context_length)
+            {
+                bail!(
+                    "Model {} has a max output outside of its context window",
+                    model.identifier
+                );
+            }
+            if model.capabilities.reasoning_effort && !model.is_reasoning {
+                bail!(
+                    "Model {} supports reasoning effort, but is not a reasoning model",
+                    model.identifier
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the effective catalog from the configured one and the provider's model list:
+    /// OpenRouter models missing upstream are marked unavailable, context length and pricing
+    /// are taken from upstream, and allowlisted models that are not in the catalog are added.
+    pub fn merged(&self, upstream: &[OpenAIModel]) -> Self {
+        let upstream: HashMap<&str, &OpenAIModel> = upstream
+            .iter()
+            .map(|model| (model.id.as_str(), model))
+            .collect();
+
+        let mut models: Vec<Model> = self
+            .catalog
+            .iter()
+            .cloned()
+            .map(|mut model| {
+                if !matches!(model.provider, InferenceProvider::OpenRouter) {
+                    return model;
+                }
+                match upstream.get(model.identifier.as_str()) {
+                    Some(remote) => {
+                        model.available = true;
+                        model.apply_upstream(remote);
+                    }
+                    None => model.available = false,
+                }
+                model
+            })
+            .collect();
+
+        for identifier in self.sync.allowlist.iter() {
+            if self
+                .catalog
+                .iter()
+                .any(|model| &model.identifier == identifier)
+            {
+                continue;
+            }
+            if let Some(remote) = upstream.get(identifier.as_str()) {
+                models.push(Model::discovered(remote));
+            }
         }
+
+        Self::from_models(self.catalog.clone(), models, self.sync.clone())
+    }
+
+    pub fn sync(&self) -> &SyncConfig {
+        &self.sync
     }
 
     pub fn free_models(&self) -> &[Model] {
@@ -164,13 +207,162 @@ impl ModelsConfig {
     pub fn paid_models(&self) -> &[Model] {
         &self.paid_models
     }
+
+    pub fn find(&self, identifier: &str) -> Option<&Model> {
+        self.free_models
+            .iter()
+            .chain(self.paid_models.iter())
+            .find(|model| model.identifier == identifier)
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Model {
     pub name: String,
     pub identifier: String,
     pub provider: InferenceProvider,
     pub is_reasoning: bool,
     pub author: String,
+    pub tiers: Vec<ModelTier>,
+    pub context_length: u32,
+    #[serde(default)]
+    pub pricing: ModelPricing,
+    #[serde(default)]
+    pub capabilities: ModelCapabilities,
+    /// Cleared by the sync job when the provider no longer serves the model.
+    #[serde(default = "Model::default_available", skip_deserializing)]
+    pub available: bool,
+}
+
+impl Model {
+    fn default_available() -> bool {
+        true
+    }
+
+    fn apply_upstream(&mut self, remote: &OpenAIModel) {
+        if let Some(context_length) = remote.context_length.filter(|length| *length > 0) {
+            self.context_length = context_length;
+        }
+        if let Some(pricing) = remote
+            .pricing
+            .as_ref()
+            .and_then(ModelPricing::from_upstream)
+        {
+            self.pricing = pricing;
+        }
+        if let Some(max_output) = remote
+            .top_provider
+            .as_ref()
+            .and_then(|provider| provider.max_completion_tokens)
+        {
+            self.capabilities.max_output = Some(max_output);
+        }
+    }
+
+    /// Whether the reasoning effort from a request should be forwarded to this model.
+    pub fn accepts_reasoning_effort(&self) -> bool {
+        self.is_reasoning && self.capabilities.reasoning_effort
+    }
+
+    fn discovered(remote: &OpenAIModel) -> Self {
+        let display_name = remote.name.clone().unwrap_or_else(|| remote.id.clone());
+        // OpenRouter names models as "Author: Model Name"
+        let (author, name) = match display_name.split_once(": ") {
+            Some((author, name)) => (author.to_string(), name.to_string()),
+            None => (
+                remote
+                    .id
+                    .split_once('/')
+                    .map(|(author, _)| author.to_string())
+                    .unwrap_or_default(),
+                display_name,
+            ),
+        };
+        let mut model = Self {
+            name,
+            identifier: remote.id.clone(),
+            provider: InferenceProvider::OpenRouter,
+            is_reasoning: remote
+                .supported_parameters
+                .iter()
+                .any(|param| param == "reasoning"),
+            author,
+            tiers: vec![if remote.id.ends_with(":free") {
+                ModelTier::Free
+            } else {
+                ModelTier::Paid
+            }],
+            context_length: 0,
+            pricing: ModelPricing::default(),
+            capabilities: ModelCapabilities::from_upstream(remote),
+            available: true,
+        };
+        model.apply_upstream(remote);
+
+        model
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelTier {
+    Free,
+    Paid,
+}
+
+/// USD per million tokens.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub prompt: f64,
+    pub completion: f64,
+}
+
+impl ModelPricing {
+    fn from_upstream(pricing: &ai::openai::models::OpenAIModelPricing) -> Option<Self> {
+        Some(Self {
+            prompt: pricing.prompt.parse::<f64>().ok()? * 1_000_000.,
+            completion: pricing.completion.parse::<f64>().ok()? * 1_000_000.,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+    /// Accepts image inputs.
+    #[serde(default)]
+    pub vision: bool,
+    /// Accepts PDF inputs.
+    #[serde(default)]
+    pub pdf: bool,
+    /// Supports tool calling.
+    #[serde(default)]
+    pub tools: bool,
+    /// Accepts a reasoning effort level. Only meaningful for reasoning models.
+    #[serde(default)]
+    pub reasoning_effort: bool,
+    /// Upper bound on generated tokens, if the provider enforces one.
+    #[serde(default)]
+    pub max_output: Option<u32>,
+}
+
+impl ModelCapabilities {
+    fn from_upstream(remote: &OpenAIModel) -> Self {
+        let input_modalities = remote
+            .architecture
+            .as_ref()
+            .map(|architecture| architecture.input_modalities.as_slice())
+            .unwrap_or_default();
+        let supports = |param: &str| remote.supported_parameters.iter().any(|p| p == param);
+
+        Self {
+            vision: input_modalities.iter().any(|modality| modality == "image"),
+            pdf: input_modalities.iter().any(|modality| modality == "file"),
+            tools: supports("tools"),
+            reasoning_effort: supports("reasoning"),
+            max_output: remote
+                .top_provider
+                .as_ref()
+                .and_then(|provider| provider.max_completion_tokens),
+        }
+    }
 }
@@ -0,0 +1,48 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::signal::unix::{SignalKind, signal};
+
+use crate::{models::ModelsConfig, state::AppState};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reloads the model catalog when the config file changes on disk or the process receives SIGHUP.
+/// An invalid catalog is logged and ignored, so the last valid one stays in effect.
+pub fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let path = ModelsConfig::config_path();
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => Some(hangup),
+            Err(e) => {
+                tracing::warn!("Failed to install SIGHUP handler: {e}");
+                None
+            }
+        };
+        let mut last_modified = modified_at(&path).await;
+
+        loop {
+            let forced = tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => false,
+                Some(_) = async { hangup.as_mut()?.recv().await } => true,
+            };
+
+            let modified = modified_at(&path).await;
+            if !forced && modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            match ModelsConfig::load(&path) {
+                Ok(config) => {
+                    state.set_models(config);
+                    tracing::info!("Reloaded model catalog from {path}.");
+                }
+                Err(e) => tracing::error!("Failed to reload model catalog: {e:#}"),
+            }
+        }
+    });
+}
+
+async fn modified_at(path: &str) -> Option<std::time::SystemTime> {
+    tokio::fs::metadata(path).await.ok()?.modified().ok()
+}
@@ -0,0 +1,25 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::state::AppState;
+
+/// Periodically pulls the OpenRouter model list and merges it into the effective catalog.
+/// The interval and allowlist are read from the catalog on every iteration, so they follow reloads.
+pub fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let sync = state.models().sync().clone();
+
+            if sync.enabled {
+                match state.inference().openrouter.clone().models().await {
+                    Ok(upstream) => {
+                        tracing::info!("Synced {} models from OpenRouter.", upstream.len());
+                        state.set_upstream_models(upstream);
+                    }
+                    Err(e) => tracing::error!("Failed to sync models from OpenRouter: {e}"),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(sync.interval_secs)).await;
+        }
+    });
+}
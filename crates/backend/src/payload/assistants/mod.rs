@@ -0,0 +1,58 @@
+use chrono::Utc;
+use model::assistant::{Assistant, AssistantTools};
+use mongodb::bson::oid::ObjectId;
+use serde::{Serialize, Serializer};
+
+use crate::payload::chat::ChatSettingsPayload;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssistantPayload {
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub id: ObjectId,
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub user_id: ObjectId,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub settings: ChatSettingsPayload,
+    pub tools: AssistantToolsPayload,
+    #[serde(serialize_with = "serialize_oids")]
+    pub knowledge: Vec<ObjectId>,
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AssistantToolsPayload {
+    pub search: bool,
+    pub memories: bool,
+}
+
+impl From<AssistantTools> for AssistantToolsPayload {
+    fn from(tools: AssistantTools) -> Self {
+        Self {
+            search: tools.search,
+            memories: tools.memories,
+        }
+    }
+}
+
+impl From<Assistant> for AssistantPayload {
+    fn from(assistant: Assistant) -> Self {
+        Self {
+            id: assistant.id.unwrap(),
+            user_id: assistant.user_id,
+            name: assistant.name,
+            avatar: assistant.avatar,
+            settings: assistant.settings.into(),
+            tools: assistant.tools.into(),
+            knowledge: assistant.knowledge,
+            timestamp: assistant.timestamp,
+        }
+    }
+}
+
+fn serialize_oids<S>(oids: &[ObjectId], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(oids.iter().map(|oid| oid.to_hex()))
+}
@@ -1,5 +1,8 @@
 use chrono::Utc;
-use model::message::Role;
+use model::{
+    chat::{Chat, ChatSettings, ReasoningEffort},
+    message::{ChatMessage, ChatMessageContent, Citation, Role},
+};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +14,36 @@ pub struct ChatPayload {
     #[serde(serialize_with = "super::serialize_oid")]
     pub user_id: ObjectId,
     pub timestamp: chrono::DateTime<Utc>,
+    #[serde(serialize_with = "super::serialize_option_oid")]
+    pub assistant_id: Option<ObjectId>,
+    #[serde(serialize_with = "super::serialize_option_oid")]
+    pub project_id: Option<ObjectId>,
+    #[serde(serialize_with = "super::serialize_option_oid")]
+    pub folder_id: Option<ObjectId>,
+    pub pinned: bool,
+    pub archived: bool,
+    pub tags: Vec<String>,
+    pub updated_at: chrono::DateTime<Utc>,
+    pub deleted_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl From<Chat> for ChatPayload {
+    fn from(chat: Chat) -> Self {
+        Self {
+            updated_at: chat.updated_at(),
+            id: chat.id.unwrap(),
+            name: chat.name,
+            timestamp: chat.timestamp,
+            user_id: chat.user_id,
+            assistant_id: chat.assistant_id,
+            project_id: chat.project_id,
+            folder_id: chat.folder_id,
+            pinned: chat.pinned,
+            archived: chat.archived,
+            tags: chat.tags,
+            deleted_at: chat.deleted_at,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +58,84 @@ pub struct ChatMessagePayload {
     #[serde(serialize_with = "super::serialize_oid")]
     pub chat_id: ObjectId,
     pub timestamp: chrono::DateTime<Utc>,
+    pub citations: Vec<CitationPayload>,
+}
+
+impl From<ChatMessage> for ChatMessagePayload {
+    fn from(message: ChatMessage) -> Self {
+        Self {
+            id: message.id.unwrap(),
+            content: message
+                .content
+                .into_iter()
+                .map(ChatMessageContentPayload::from)
+                .collect(),
+            model: message.model,
+            reasoning: message.reasoning,
+            role: message.role,
+            updated_memory: message.updated_memory,
+            chat_id: message.chat_id,
+            timestamp: message.timestamp,
+            citations: message
+                .citations
+                .into_iter()
+                .map(CitationPayload::from)
+                .collect(),
+        }
+    }
+}
+
+/// Page of a user's chats, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatListPayload {
+    pub chats: Vec<ChatPayload>,
+    /// Passed as `cursor` to fetch the next page, absent on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Window of a chat's messages, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessagesPayload {
+    pub messages: Vec<ChatMessagePayload>,
+    /// Passed as `before` to fetch older messages, absent when the window reaches the first one.
+    pub before: Option<String>,
+    /// Passed as `after` to fetch newer messages, absent when the window reaches the last one.
+    pub after: Option<String>,
+}
+
+/// Chat kept only in Redis until it expires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporaryChatPayload {
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub id: ObjectId,
+    pub timestamp: chrono::DateTime<Utc>,
+    /// Seconds the chat is kept after its last message.
+    pub ttl: u64,
+    /// Oldest first.
+    pub messages: Vec<ChatMessagePayload>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitationPayload {
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub upload_id: ObjectId,
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub chunk_id: ObjectId,
+    pub page: Option<u32>,
+    pub snippet: String,
+    pub score: f32,
+}
+
+impl From<Citation> for CitationPayload {
+    fn from(citation: Citation) -> Self {
+        Self {
+            upload_id: citation.upload_id,
+            chunk_id: citation.chunk_id,
+            page: citation.page,
+            snippet: citation.snippet,
+            score: citation.score,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
@@ -42,3 +153,47 @@ pub enum ChatMessageContentPayload {
         id: ObjectId,
     },
 }
+
+impl From<ChatMessageContent> for ChatMessageContentPayload {
+    fn from(content: ChatMessageContent) -> Self {
+        match content {
+            ChatMessageContent::Text { value } => Self::Text { value },
+            ChatMessageContent::Image { id } => Self::Image { id },
+            ChatMessageContent::Pdf { id } => Self::Pdf { id },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSummaryPayload {
+    pub content: String,
+    pub covers_until: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSettingsPayload {
+    pub system_prompt: Option<String>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub stop: Vec<String>,
+    pub seed: Option<i64>,
+    pub default_model: Option<String>,
+    pub default_reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl From<ChatSettings> for ChatSettingsPayload {
+    fn from(settings: ChatSettings) -> Self {
+        Self {
+            system_prompt: settings.system_prompt,
+            temperature: settings.temperature,
+            top_p: settings.top_p,
+            max_tokens: settings.max_tokens,
+            stop: settings.stop,
+            seed: settings.seed,
+            default_model: settings.default_model,
+            default_reasoning_effort: settings.default_reasoning_effort,
+        }
+    }
+}
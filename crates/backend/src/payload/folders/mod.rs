@@ -0,0 +1,25 @@
+use chrono::Utc;
+use model::folder::Folder;
+use mongodb::bson::oid::ObjectId;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderPayload {
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub id: ObjectId,
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub user_id: ObjectId,
+    pub name: String,
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+impl From<Folder> for FolderPayload {
+    fn from(folder: Folder) -> Self {
+        Self {
+            id: folder.id.unwrap(),
+            user_id: folder.user_id,
+            name: folder.name,
+            timestamp: folder.timestamp,
+        }
+    }
+}
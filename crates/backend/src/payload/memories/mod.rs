@@ -1,9 +1,40 @@
+use chrono::Utc;
+use model::memory::Memory;
 use mongodb::bson::oid::ObjectId;
 use serde::Serialize;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MemoryPayload {
     #[serde(serialize_with = "super::serialize_oid")]
     pub id: ObjectId,
     pub content: String,
+    #[serde(serialize_with = "super::serialize_option_oid")]
+    pub project_id: Option<ObjectId>,
+    pub tags: Vec<String>,
+    pub pinned: bool,
+    #[serde(serialize_with = "super::serialize_option_oid")]
+    pub source_message_id: Option<ObjectId>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl From<Memory> for MemoryPayload {
+    fn from(memory: Memory) -> Self {
+        let id = memory.id.unwrap();
+        // memories saved before timestamps were recorded fall back to the id's creation time
+        let created_at = memory
+            .created_at
+            .unwrap_or_else(|| id.timestamp().to_chrono());
+
+        Self {
+            id,
+            content: memory.content,
+            project_id: memory.project_id,
+            tags: memory.tags,
+            pinned: memory.pinned,
+            source_message_id: memory.source_message_id,
+            created_at,
+            updated_at: memory.updated_at.unwrap_or(created_at),
+        }
+    }
 }
@@ -1,10 +1,15 @@
 use mongodb::bson::oid::ObjectId;
 use serde::Serializer;
 
+pub mod assistants;
 pub mod auth;
 pub mod chat;
+pub mod folders;
 pub mod memories;
+pub mod projects;
+pub mod search;
 pub mod upload;
+pub mod users;
 
 pub fn serialize_oid<S>(oid: &ObjectId, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -0,0 +1,29 @@
+use chrono::Utc;
+use model::project::Project;
+use mongodb::bson::oid::ObjectId;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectPayload {
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub id: ObjectId,
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub user_id: ObjectId,
+    pub name: String,
+    pub instructions: Option<String>,
+    pub scoped_memories: bool,
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+impl From<Project> for ProjectPayload {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id.unwrap(),
+            user_id: project.user_id,
+            name: project.name,
+            instructions: project.instructions,
+            scoped_memories: project.scoped_memories,
+            timestamp: project.timestamp,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::ops::Range;
+
+use chrono::Utc;
+use model::message::Role;
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::payload::chat::ChatPayload;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResultsPayload {
+    pub results: Vec<ChatSearchResultPayload>,
+    /// Passed as `cursor` to fetch the next page, absent on the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSearchResultPayload {
+    pub chat: ChatPayload,
+    /// Matches of the query in the chat's name.
+    pub name_highlights: Vec<HighlightPayload>,
+    pub snippets: Vec<MessageSnippetPayload>,
+    /// Similarity of the best matching message, set by semantic search.
+    pub score: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSnippetPayload {
+    #[serde(serialize_with = "super::serialize_oid")]
+    pub message_id: ObjectId,
+    pub role: Role,
+    pub model: Option<String>,
+    pub timestamp: chrono::DateTime<Utc>,
+    pub text: String,
+    pub highlights: Vec<HighlightPayload>,
+    /// Similarity of the message to the query, set by semantic search.
+    pub score: Option<f32>,
+}
+
+/// Char range of a match, end exclusive.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HighlightPayload {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<Range<usize>> for HighlightPayload {
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
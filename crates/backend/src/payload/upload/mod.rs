@@ -1,7 +1,8 @@
+use model::upload::IngestionStatus;
 use mongodb::bson::oid::ObjectId;
 use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct UserUploadPayload {
     #[serde(serialize_with = "super::serialize_oid")]
     pub id: ObjectId,
@@ -10,4 +11,5 @@ pub struct UserUploadPayload {
     #[serde(serialize_with = "super::serialize_option_oid")]
     pub chat_id: Option<ObjectId>,
     pub content_type: String,
+    pub ingestion: Option<IngestionStatus>,
 }
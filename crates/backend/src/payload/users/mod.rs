@@ -0,0 +1,25 @@
+use model::user::UserSettings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSettingsPayload {
+    pub about_me: Option<String>,
+    pub response_instructions: Option<String>,
+    pub default_model: Option<String>,
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    pub retention_days: Option<u32>,
+}
+
+impl From<UserSettings> for UserSettingsPayload {
+    fn from(settings: UserSettings) -> Self {
+        Self {
+            about_me: settings.about_me,
+            response_instructions: settings.response_instructions,
+            default_model: settings.default_model,
+            timezone: settings.timezone,
+            locale: settings.locale,
+            retention_days: settings.retention_days,
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::{env, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use model::{
+    chat::Chat,
+    memory::Memory,
+    message::{ChatMessage, ChatMessageContent},
+    retention::RetentionAudit,
+    upload::UserUpload,
+    user::{User, UserSettings},
+    vector::VectorNamespace,
+};
+use mongodb::bson::{self, Bson, doc, oid::ObjectId};
+
+use crate::{documents, state::AppState, trash};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Longest any data is kept, from `RETENTION_DAYS`. Unset keeps data until users delete it.
+pub fn deployment_days() -> anyhow::Result<Option<u32>> {
+    let days = env::var("RETENTION_DAYS")
+        .ok()
+        .map(|days| days.parse::<u32>())
+        .transpose()
+        .context("Invalid retention days")?;
+    if days == Some(0) {
+        anyhow::bail!("Retention days must be at least 1");
+    }
+
+    Ok(days)
+}
+
+/// Retention period applied to a user: the shorter of theirs and the deployment's.
+pub fn effective_days(settings: &UserSettings, deployment_days: Option<u32>) -> Option<u32> {
+    [settings.retention_days, deployment_days]
+        .into_iter()
+        .flatten()
+        .min()
+}
+
+/// Purges chats, uploads and memories that outlived their retention period, every hour.
+/// Fails when the deployment's retention period is misconfigured.
+pub fn spawn_sweep(state: Arc<AppState>) -> anyhow::Result<()> {
+    let deployment_days = deployment_days()?;
+    tokio::spawn(async move {
+        loop {
+            match sweep(&state, deployment_days).await {
+                Ok(0) => {}
+                Ok(users) => tracing::info!("Purged expired data of {users} users."),
+                Err(e) => tracing::error!("Failed to purge expired data: {e}"),
+            }
+            tokio::time::sleep(SWEEP_INTERVAL).await;
+        }
+    });
+
+    Ok(())
+}
+
+async fn sweep(state: &AppState, deployment_days: Option<u32>) -> anyhow::Result<usize> {
+    // without a deployment policy only users who chose one are swept
+    let filter = match deployment_days {
+        Some(_) => doc! {},
+        None => doc! { "settings.retention_days": { "$ne": null } },
+    };
+    let users: Vec<User> = state
+        .storage()
+        .database()
+        .users
+        .get_many(filter)
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut swept = 0;
+    for user in users {
+        let Some(days) = effective_days(&user.settings, deployment_days) else {
+            continue;
+        };
+        match sweep_user(state, user.id.unwrap(), days).await {
+            Ok(true) => swept += 1,
+            Ok(false) => {}
+            Err(e) => tracing::error!("Failed to purge expired data of user {:?}: {e}", user.id),
+        }
+    }
+
+    Ok(swept)
+}
+
+/// Purges the user's data last active before the retention period and records an audit when
+/// anything was purged. Items that fail to purge are logged and retried on the next sweep, and
+/// whatever was purged before a failure is still audited.
+async fn sweep_user(state: &AppState, user_id: ObjectId, days: u32) -> anyhow::Result<bool> {
+    let cutoff = Utc::now() - chrono::Duration::days(days.into());
+    let mut audit = RetentionAudit {
+        id: None,
+        user_id,
+        policy_days: days,
+        cutoff,
+        chat_ids: vec![],
+        messages: 0,
+        upload_ids: vec![],
+        memory_ids: vec![],
+        timestamp: Utc::now(),
+    };
+
+    if let Err(e) = purge_chats(state, user_id, cutoff, &mut audit).await {
+        tracing::error!("Failed to purge expired chats of user {user_id}: {e}");
+    }
+    if let Err(e) = purge_messages(state, user_id, cutoff, &mut audit).await {
+        tracing::error!("Failed to purge expired messages of user {user_id}: {e}");
+    }
+    if let Err(e) = purge_uploads(state, user_id, cutoff, &mut audit).await {
+        tracing::error!("Failed to purge expired uploads of user {user_id}: {e}");
+    }
+    match purge_memories(state, user_id, cutoff).await {
+        Ok(ids) => audit.memory_ids = ids,
+        Err(e) => tracing::error!("Failed to purge expired memories of user {user_id}: {e}"),
+    }
+
+    if audit.chat_ids.is_empty()
+        && audit.messages == 0
+        && audit.upload_ids.is_empty()
+        && audit.memory_ids.is_empty()
+    {
+        return Ok(false);
+    }
+    state
+        .storage()
+        .database()
+        .retention_audits
+        .create(audit)
+        .await?;
+
+    Ok(true)
+}
+
+/// Purges the chats last active before `cutoff`, including chats in the trash, which would
+/// otherwise wait for the trash retention.
+async fn purge_chats(
+    state: &AppState,
+    user_id: ObjectId,
+    cutoff: DateTime<Utc>,
+    audit: &mut RetentionAudit,
+) -> anyhow::Result<()> {
+    let expired = doc! { "updated_at": { "$lte": bson::DateTime::from_chrono(cutoff) } };
+    let mut filter = expired.clone();
+    filter.insert("user_id", user_id);
+    let chats: Vec<Chat> = state
+        .storage()
+        .database()
+        .chats
+        .get_many(filter)
+        .await?
+        .try_collect()
+        .await?;
+    for chat in chats {
+        match trash::purge(state, &chat, expired.clone()).await {
+            Ok(None) => {}
+            Ok(Some(purged)) => {
+                audit.chat_ids.push(chat.id.unwrap());
+                audit.messages += purged.messages;
+                audit.upload_ids.extend(purged.upload_ids);
+            }
+            Err(e) => tracing::error!("Failed to purge expired chat {:?}: {e}", chat.id),
+        }
+    }
+
+    Ok(())
+}
+
+/// Purges the messages sent before `cutoff` in chats that are still active, with their
+/// embeddings and the uploads they carried.
+async fn purge_messages(
+    state: &AppState,
+    user_id: ObjectId,
+    cutoff: DateTime<Utc>,
+    audit: &mut RetentionAudit,
+) -> anyhow::Result<()> {
+    let database = state.storage().database();
+    let chat_ids = database
+        .chats
+        .collection()
+        .distinct("_id", doc! { "user_id": user_id })
+        .await?;
+    let messages: Vec<ChatMessage> = database
+        .messages
+        .get_many(doc! {
+            "chat_id": { "$in": chat_ids },
+            "timestamp": { "$lte": bson::DateTime::from_chrono(cutoff) },
+        })
+        .await?
+        .try_collect()
+        .await?;
+    let ids: Vec<ObjectId> = messages.iter().filter_map(|message| message.id).collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    state
+        .storage()
+        .vectors()
+        .delete(VectorNamespace::Messages, &ids)
+        .await?;
+    audit.messages += database
+        .messages
+        .collection()
+        .delete_many(doc! { "_id": { "$in": &ids } })
+        .await?
+        .deleted_count;
+
+    // assistant and project files are kept with their owner
+    let attachments: Vec<ObjectId> = messages
+        .iter()
+        .flat_map(|message| &message.content)
+        .filter_map(|content| match content {
+            ChatMessageContent::Image { id } | ChatMessageContent::Pdf { id } => Some(*id),
+            ChatMessageContent::Text { .. } => None,
+        })
+        .collect();
+    let uploads: Vec<UserUpload> = database
+        .uploads
+        .get_many(doc! {
+            "_id": { "$in": attachments },
+            "user_id": user_id,
+            "assistant_id": null,
+            "project_id": null,
+        })
+        .await?
+        .try_collect()
+        .await?;
+    for upload in uploads {
+        match purge_upload(state, &upload).await {
+            Ok(()) => audit.upload_ids.push(upload.id),
+            Err(e) => tracing::error!("Failed to purge expired upload {}: {e}", upload.id),
+        }
+    }
+
+    Ok(())
+}
+
+/// Purges uploads never sent in a chat. Assistant and project files are kept with their owner.
+async fn purge_uploads(
+    state: &AppState,
+    user_id: ObjectId,
+    cutoff: DateTime<Utc>,
+    audit: &mut RetentionAudit,
+) -> anyhow::Result<()> {
+    let uploads: Vec<UserUpload> = state
+        .storage()
+        .database()
+        .uploads
+        .get_many(doc! {
+            "user_id": user_id,
+            "chat_id": null,
+            "assistant_id": null,
+            "project_id": null,
+            "_id": { "$lt": created_before(cutoff) },
+        })
+        .await?
+        .try_collect()
+        .await?;
+    for upload in uploads {
+        match purge_upload(state, &upload).await {
+            Ok(()) => audit.upload_ids.push(upload.id),
+            Err(e) => tracing::error!("Failed to purge expired upload {}: {e}", upload.id),
+        }
+    }
+
+    Ok(())
+}
+
+async fn purge_upload(state: &AppState, upload: &UserUpload) -> anyhow::Result<()> {
+    documents::remove(state, upload.id).await?;
+    state
+        .storage()
+        .bucket()
+        .gridfs()
+        .delete(Bson::ObjectId(upload.id))
+        .await?;
+    state.storage().database().uploads.delete(upload.id).await
+}
+
+/// Deletes the memories last updated before `cutoff`. Memories without timestamps are dated by
+/// their id.
+async fn purge_memories(
+    state: &AppState,
+    user_id: ObjectId,
+    cutoff: DateTime<Utc>,
+) -> anyhow::Result<Vec<ObjectId>> {
+    let before = bson::DateTime::from_chrono(cutoff);
+    let memories: Vec<Memory> = state
+        .storage()
+        .database()
+        .memories
+        .get_many(doc! {
+            "user_id": user_id,
+            "$or": [
+                { "updated_at": { "$lte": before } },
+                { "updated_at": null, "created_at": { "$lte": before } },
+                {
+                    "updated_at": null,
+                    "created_at": null,
+                    "_id": { "$lt": created_before(cutoff) },
+                },
+            ],
+        })
+        .await?
+        .try_collect()
+        .await?;
+    let ids: Vec<ObjectId> = memories.iter().filter_map(|memory| memory.id).collect();
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+
+    state
+        .storage()
+        .vectors()
+        .delete(VectorNamespace::Memories, &ids)
+        .await?;
+    state
+        .storage()
+        .database()
+        .memories
+        .delete_many(doc! { "_id": { "$in": &ids } })
+        .await?;
+
+    Ok(ids)
+}
+
+/// Smallest id generated at `time`, so `$lt` matches documents created before it.
+fn created_before(time: DateTime<Utc>) -> ObjectId {
+    let mut bytes = [0; 12];
+    bytes[..4].copy_from_slice(&(time.timestamp() as u32).to_be_bytes());
+    ObjectId::from_bytes(bytes)
+}
@@ -0,0 +1,99 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use model::assistant::{Assistant, AssistantTools};
+use mongodb::bson::oid::ObjectId;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    context::assistant,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::assistants::AssistantPayload,
+    routes::chats::update_settings::UpdateChatSettingsPayload,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AssistantBody {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1-64 chars long."))]
+    pub name: String,
+    #[validate(length(max = 2048, message = "Avatar must be at most 2048 chars long."))]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub settings: UpdateChatSettingsPayload,
+    #[serde(default)]
+    pub tools: AssistantTools,
+    #[serde(default)]
+    #[validate(length(max = 20, message = "At most 20 knowledge files are allowed."))]
+    pub knowledge: Vec<ObjectId>,
+}
+
+impl AssistantBody {
+    /// Validates the body and the model and knowledge files it references. `assistant_id` is
+    /// the assistant being updated, whose own knowledge files may be kept.
+    pub async fn check(
+        &mut self,
+        state: &AppState,
+        user_id: ObjectId,
+        assistant_id: Option<ObjectId>,
+    ) -> Result<(), ApplicationError> {
+        let mut seen = HashSet::new();
+        self.knowledge.retain(|id| seen.insert(*id));
+
+        if let Err(errors) = self.validate() {
+            return Err(ApplicationError::ValidationError(errors));
+        }
+
+        if self
+            .settings
+            .default_model
+            .as_ref()
+            .is_some_and(|identifier| state.models().find(identifier).is_none())
+        {
+            return Err(ApplicationError::InvalidModelIdentifier);
+        }
+
+        assistant::validate_knowledge(state, user_id, assistant_id, &self.knowledge).await
+    }
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Json(mut payload): Json<AssistantBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    payload.check(&state, session.user_id, None).await?;
+
+    let mut assistant = Assistant {
+        id: None,
+        user_id: session.user_id,
+        name: payload.name.trim().to_string(),
+        avatar: payload.avatar.filter(|avatar| !avatar.trim().is_empty()),
+        settings: payload.settings.into_settings(),
+        tools: payload.tools,
+        knowledge: payload.knowledge,
+        timestamp: Utc::now(),
+    };
+
+    let id = state
+        .storage()
+        .database()
+        .assistants
+        .create(assistant.clone())
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    assistant.id = Some(id);
+
+    assistant::pin_knowledge(&state, id, &[], &assistant.knowledge).await?;
+
+    Ok((StatusCode::OK, Json(AssistantPayload::from(assistant))).into_response())
+}
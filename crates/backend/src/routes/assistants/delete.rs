@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::{share::Share, vector::VectorNamespace};
+use mongodb::{
+    ClientSession,
+    bson::{doc, oid::ObjectId},
+};
+use redis_om::HashModel;
+use reqwest::StatusCode;
+
+use crate::{
+    data::transaction,
+    documents,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    state::AppState,
+};
+
+/// Deletes the assistant with the knowledge files only it uses. Files that were also sent in a
+/// chat or added to a project are kept and only detached from the assistant.
+///
+/// The rows are changed in one transaction when the server supports them. Otherwise the
+/// assistant is deleted last, so a request that fails halfway can be retried.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(assistant_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let assistant = state
+        .storage()
+        .database()
+        .assistants
+        .get_by_id(assistant_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    let Some(assistant) = assistant else {
+        return Err(ApplicationError::AssistantDoesNotExist);
+    };
+
+    if assistant.user_id != session.user_id {
+        return Err(ApplicationError::AssistantDoesNotBelongToUser);
+    }
+
+    let upload_ids: Vec<ObjectId> = state
+        .storage()
+        .database()
+        .uploads
+        .collection()
+        .distinct(
+            "_id",
+            doc! { "assistant_id": assistant_id, "chat_id": null, "project_id": null },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                e.into(),
+            )))
+        })?
+        .into_iter()
+        .filter_map(|id| id.as_object_id())
+        .collect();
+
+    // embeddings are not covered by the transaction, so they go first
+    for upload_id in &upload_ids {
+        state
+            .storage()
+            .vectors()
+            .delete_group(VectorNamespace::Documents, *upload_id)
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+    }
+
+    let result = match delete_in_transaction(&state, assistant_id, &upload_ids).await {
+        Err(e) if transaction::unsupported(&e) => {
+            delete_rows(&state, assistant_id, &upload_ids, None).await
+        }
+        result => result,
+    };
+    result.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            e.into(),
+        )))
+    })?;
+
+    let mut conn = state.storage().cache().connection();
+    let _ = Share::delete(assistant_id.to_hex(), &mut conn).await;
+
+    Ok(StatusCode::OK.into_response())
+}
+
+async fn delete_in_transaction(
+    state: &AppState,
+    assistant_id: ObjectId,
+    upload_ids: &[ObjectId],
+) -> mongodb::error::Result<()> {
+    let mut session = state.storage().database().client().start_session().await?;
+    session.start_transaction().await?;
+
+    match delete_rows(state, assistant_id, upload_ids, Some(&mut session)).await {
+        Ok(()) => session.commit_transaction().await,
+        Err(e) => {
+            let _ = session.abort_transaction().await;
+            Err(e)
+        }
+    }
+}
+
+/// Detaches the assistant's shared knowledge files and deletes the rest, the assistant last.
+async fn delete_rows(
+    state: &AppState,
+    assistant_id: ObjectId,
+    upload_ids: &[ObjectId],
+    mut session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<()> {
+    let database = state.storage().database();
+
+    documents::delete_rows(state, upload_ids, session.as_deref_mut()).await?;
+    transaction::update_many(
+        &database.uploads.collection(),
+        doc! { "assistant_id": assistant_id },
+        doc! { "$set": { "assistant_id": null } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    transaction::delete_many(
+        &database.assistants.collection(),
+        doc! { "_id": assistant_id },
+        session,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::assistants::AssistantPayload,
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let assistants = state
+        .storage()
+        .database()
+        .assistants
+        .get_many_sorted(doc! { "user_id": session.user_id }, doc! { "timestamp": 1 })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .map_ok(AssistantPayload::from)
+        .try_collect::<Vec<_>>()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow::anyhow!(e),
+            )))
+        })?;
+
+    Ok((StatusCode::OK, Json(assistants)).into_response())
+}
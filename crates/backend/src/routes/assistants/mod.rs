@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{delete as method_delete, get, post, put},
+};
+
+use crate::state::AppState;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod share;
+pub mod share_state;
+pub mod state;
+pub mod unshare;
+pub mod update;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/assistants", post(create::handler))
+        .route("/assistants", get(list::handler))
+        .route("/assistants/{assistant_id}", get(state::handler))
+        .route("/assistants/{assistant_id}", put(update::handler))
+        .route("/assistants/{assistant_id}", method_delete(delete::handler))
+        .route("/assistants/{assistant_id}/share", post(share::handler))
+        .route(
+            "/assistants/{assistant_id}/share",
+            get(share_state::handler),
+        )
+        .route(
+            "/assistants/{assistant_id}/share/{share_id}",
+            method_delete(unshare::handler),
+        )
+}
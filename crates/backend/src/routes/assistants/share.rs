@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::share::Share;
+use mongodb::bson::oid::ObjectId;
+use redis_om::HashModel;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, cache::CacheError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(assistant_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let assistant = state
+        .storage()
+        .database()
+        .assistants
+        .get_by_id(assistant_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    let Some(assistant) = assistant else {
+        return Err(ApplicationError::AssistantDoesNotExist);
+    };
+
+    if assistant.user_id != session.user_id {
+        return Err(ApplicationError::AssistantDoesNotBelongToUser);
+    }
+
+    let mut conn = state.storage().cache().connection();
+
+    let share_id = ObjectId::new();
+    let mut share = Share {
+        id: assistant_id.to_hex(),
+        share_id: share_id.to_hex(),
+    };
+
+    share.save(&mut conn).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::CacheError(CacheError::Unknown(e)))
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "id": share_id.to_hex() }))).into_response())
+}
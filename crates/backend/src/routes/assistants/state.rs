@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::{
+    context::assistant, errors::ApplicationError, middleware::auth::Auth,
+    payload::assistants::AssistantPayload, state::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct GetAssistantStateQuery {
+    share_id: Option<ObjectId>,
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(assistant_id): Path<ObjectId>,
+    Query(payload): Query<GetAssistantStateQuery>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let assistant =
+        assistant::load_accessible(&state, assistant_id, session.user_id, payload.share_id).await?;
+
+    Ok((StatusCode::OK, Json(AssistantPayload::from(assistant))).into_response())
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::{self, doc, oid::ObjectId};
+use reqwest::StatusCode;
+
+use crate::{
+    context::assistant,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::assistants::AssistantPayload,
+    routes::assistants::create::AssistantBody,
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(assistant_id): Path<ObjectId>,
+    Json(mut payload): Json<AssistantBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let existing = state
+        .storage()
+        .database()
+        .assistants
+        .get_by_id(assistant_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    let Some(existing) = existing else {
+        return Err(ApplicationError::AssistantDoesNotExist);
+    };
+
+    if existing.user_id != session.user_id {
+        return Err(ApplicationError::AssistantDoesNotBelongToUser);
+    }
+
+    payload
+        .check(&state, session.user_id, Some(assistant_id))
+        .await?;
+
+    let assistant = model::assistant::Assistant {
+        name: payload.name.trim().to_string(),
+        avatar: payload.avatar.filter(|avatar| !avatar.trim().is_empty()),
+        settings: payload.settings.into_settings(),
+        tools: payload.tools,
+        knowledge: payload.knowledge,
+        ..existing.clone()
+    };
+    let settings_bson = bson::to_bson(&assistant.settings).map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            anyhow::anyhow!(e),
+        )))
+    })?;
+    let tools_bson = bson::to_bson(&assistant.tools).map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            anyhow::anyhow!(e),
+        )))
+    })?;
+
+    state
+        .storage()
+        .database()
+        .assistants
+        .update(
+            assistant_id,
+            doc! {
+                "$set": {
+                    "name": &assistant.name,
+                    "avatar": &assistant.avatar,
+                    "settings": settings_bson,
+                    "tools": tools_bson,
+                    "knowledge": &assistant.knowledge,
+                }
+            },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    assistant::pin_knowledge(
+        &state,
+        assistant_id,
+        &existing.knowledge,
+        &assistant.knowledge,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(AssistantPayload::from(assistant))).into_response())
+}
@@ -41,6 +41,7 @@ pub async fn handler(
         id: None,
         email: payload.email,
         password: hashed_password,
+        settings: Default::default(),
     };
 
     if let Err(e) = state.storage().database().users.create(user).await {
@@ -50,10 +51,7 @@ pub async fn handler(
             .kind
             .as_ref()
         {
-            ErrorKind::Write(err) => match err {
-                WriteFailure::WriteError(err) => err.code == 11000,
-                _ => false,
-            },
+            ErrorKind::Write(WriteFailure::WriteError(err)) => err.code == 11000,
             _ => false,
         };
         if exists {
@@ -3,8 +3,11 @@ use std::sync::Arc;
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use chrono::Utc;
 use model::chat::Chat;
+use mongodb::bson::oid::ObjectId;
+use serde::Deserialize;
 
 use crate::{
+    context::{assistant, project},
     errors::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
@@ -14,15 +17,51 @@ use crate::{
     state::AppState,
 };
 
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateChatPayload {
+    pub assistant_id: Option<ObjectId>,
+    /// Required to start a chat with an assistant shared by another user.
+    pub share_id: Option<ObjectId>,
+    pub project_id: Option<ObjectId>,
+}
+
 pub async fn handler(
     State(state): State<Arc<AppState>>,
     Auth(session): Auth,
+    payload: Option<Json<CreateChatPayload>>,
 ) -> Result<impl IntoResponse, ApplicationError> {
-    let chat = Chat {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+    let mut share_id = None;
+    if let Some(assistant_id) = payload.assistant_id {
+        let assistant =
+            assistant::load_accessible(&state, assistant_id, session.user_id, payload.share_id)
+                .await?;
+        if assistant.user_id != session.user_id {
+            share_id = payload.share_id;
+        }
+    }
+    if let Some(project_id) = payload.project_id {
+        project::load_owned(&state, project_id, session.user_id).await?;
+    }
+
+    let now = Utc::now();
+    let mut chat = Chat {
         id: None,
         name: None,
         user_id: session.user_id,
-        timestamp: Utc::now(),
+        timestamp: now,
+        summary: None,
+        settings: Default::default(),
+        assistant_id: payload.assistant_id,
+        share_id,
+        project_id: payload.project_id,
+        renamed: false,
+        folder_id: None,
+        pinned: false,
+        archived: false,
+        tags: vec![],
+        updated_at: Some(now),
+        deleted_at: None,
     };
 
     let id = state
@@ -34,15 +73,7 @@ pub async fn handler(
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
+    chat.id = Some(id);
 
-    Ok((
-        StatusCode::OK,
-        Json(ChatPayload {
-            id,
-            name: chat.name,
-            user_id: chat.user_id,
-            timestamp: chat.timestamp,
-        }),
-    )
-        .into_response())
+    Ok((StatusCode::OK, Json(ChatPayload::from(chat))).into_response())
 }
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, response::IntoResponse};
+use reqwest::StatusCode;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::chat::TemporaryChatPayload,
+    state::AppState,
+    temporary,
+};
+
+/// Starts a chat that is kept only in Redis and expires after a period of inactivity.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = temporary::create(&state, session.user_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TemporaryChatPayload {
+            id: chat.id,
+            timestamp: chat.timestamp,
+            ttl: state.temporary_chat_ttl().as_secs(),
+            messages: vec![],
+        }),
+    )
+        .into_response())
+}
@@ -4,8 +4,7 @@ use axum::{
     extract::{Path, State},
     response::IntoResponse,
 };
-use futures::TryStreamExt;
-use mongodb::bson::{Bson, doc, oid::ObjectId};
+use mongodb::bson::{self, doc, oid::ObjectId};
 use reqwest::StatusCode;
 
 use crate::{
@@ -17,6 +16,7 @@ use crate::{
     state::AppState,
 };
 
+/// Moves the chat to the trash. It is purged once the retention window passes.
 pub async fn handler(
     State(state): State<Arc<AppState>>,
     Auth(session): Auth,
@@ -42,65 +42,22 @@ pub async fn handler(
             DatabaseError::ChatDoesNotBelongToUser,
         )));
     }
+    if chat.deleted_at.is_some() {
+        return Ok(StatusCode::OK.into_response());
+    }
 
     state
         .storage()
         .database()
         .chats
-        .delete(chat.id.unwrap())
+        .update(
+            chat_id,
+            doc! { "$set": { "deleted_at": bson::DateTime::now() } },
+        )
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
 
-    tokio::spawn(async move {
-        // delete messages
-        let mut messages = state
-            .storage()
-            .database()
-            .messages
-            .get_many(doc! { "chat_id": chat_id })
-            .await
-            .unwrap()
-            .into_stream();
-
-        while let Ok(Some(message)) = messages.try_next().await {
-            state
-                .storage()
-                .database()
-                .messages
-                .delete(message.id.unwrap())
-                .await
-                .unwrap();
-        }
-
-        // delete associated files
-        let mut files = state
-            .storage()
-            .database()
-            .uploads
-            .get_many(doc! { "chat_id": chat_id, "user_id": session.user_id })
-            .await
-            .unwrap()
-            .into_stream();
-
-        while let Ok(Some(file)) = files.try_next().await {
-            state
-                .storage()
-                .database()
-                .uploads
-                .delete(file.id)
-                .await
-                .unwrap();
-            state
-                .storage()
-                .bucket()
-                .gridfs()
-                .delete(Bson::ObjectId(file.id))
-                .await
-                .unwrap();
-        }
-    });
-
     Ok(StatusCode::OK.into_response())
 }
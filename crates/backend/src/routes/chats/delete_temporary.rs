@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    state::AppState,
+    temporary,
+};
+
+/// Discards a temporary chat before it expires.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = temporary::get(&state, session.user_id, chat_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    if chat.is_none() {
+        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::ChatDoesNotExist,
+        )));
+    }
+
+    temporary::delete(&state, chat_id).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+    })?;
+
+    Ok((StatusCode::OK).into_response())
+}
@@ -6,24 +6,51 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use futures::{StreamExt, TryStreamExt};
-use mongodb::bson::doc;
+use futures::TryStreamExt;
+use model::chat::Chat;
+use mongodb::bson::{Bson, doc, oid::ObjectId};
 use serde::Deserialize;
+use validator::Validate;
 
 use crate::{
+    data::cursor::Cursor,
     errors::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
     },
     middleware::auth::Auth,
-    payload::chat::ChatPayload,
+    payload::chat::{ChatListPayload, ChatPayload},
     state::AppState,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct ListChatsPayload {
-    pub start: usize,
-    pub take: usize,
+    pub cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be 1-100."))]
+    pub limit: usize,
+    /// Only lists the chats of this project when set.
+    pub project_id: Option<ObjectId>,
+    /// Only lists the chats of this folder when set.
+    pub folder_id: Option<ObjectId>,
+    pub pinned: Option<bool>,
+    /// Lists archived chats instead of the others when true.
+    #[serde(default)]
+    pub archived: bool,
+    /// Only lists chats with this tag when set.
+    pub tag: Option<String>,
+}
+
+fn default_limit() -> usize {
+    30
+}
+
+fn flag(value: bool) -> Bson {
+    if value {
+        Bson::Boolean(true)
+    } else {
+        Bson::Document(doc! { "$ne": true })
+    }
 }
 
 pub async fn handler(
@@ -31,28 +58,48 @@ pub async fn handler(
     Auth(session): Auth,
     Query(payload): Query<ListChatsPayload>,
 ) -> Result<impl IntoResponse, ApplicationError> {
-    let chats = state
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    // chats created before the flags existed have no fields, so unset flags are matched as false
+    let mut filter = doc! {
+        "user_id": session.user_id,
+        "archived": flag(payload.archived),
+        "deleted_at": null,
+    };
+    if let Some(project_id) = payload.project_id {
+        filter.insert("project_id", project_id);
+    }
+    if let Some(folder_id) = payload.folder_id {
+        filter.insert("folder_id", folder_id);
+    }
+    if let Some(pinned) = payload.pinned {
+        filter.insert("pinned", flag(pinned));
+    }
+    if let Some(tag) = payload.tag {
+        filter.insert("tags", tag.trim().to_lowercase());
+    }
+    if let Some(ref cursor) = payload.cursor {
+        let cursor = Cursor::decode(cursor).ok_or(ApplicationError::InvalidCursor)?;
+        filter.extend(cursor.older("updated_at"));
+    }
+
+    let mut chats: Vec<Chat> = state
         .storage()
         .database()
         .chats
-        .get_many_sorted(doc! { "user_id": session.user_id }, doc! { "timestamp": 1 })
+        .collection()
+        .find(filter)
+        .sort(doc! { "updated_at": -1, "_id": -1 })
+        .limit(payload.limit as i64 + 1)
         .await
         .map_err(|e| {
-            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
-        })?;
-
-    let chats = chats
-        .skip(payload.start)
-        .take(payload.take)
-        .map(|chat| {
-            chat.map(|chat| ChatPayload {
-                id: chat.id.unwrap(),
-                name: chat.name,
-                timestamp: chat.timestamp,
-                user_id: chat.user_id,
-            })
-        })
-        .try_collect::<Vec<_>>()
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow::anyhow!(e),
+            )))
+        })?
+        .try_collect()
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
@@ -60,5 +107,25 @@ pub async fn handler(
             )))
         })?;
 
-    Ok((StatusCode::OK, Json(chats)).into_response())
+    let next_cursor = if chats.len() > payload.limit {
+        chats.truncate(payload.limit);
+        chats.last().map(|chat| {
+            Cursor {
+                timestamp: chat.updated_at(),
+                id: chat.id.unwrap(),
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(ChatListPayload {
+            chats: chats.into_iter().map(ChatPayload::from).collect(),
+            next_cursor,
+        }),
+    )
+        .into_response())
 }
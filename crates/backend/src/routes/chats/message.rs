@@ -17,38 +17,45 @@ use axum::{
 };
 use base64::{Engine, prelude::BASE64_STANDARD};
 use chrono::Utc;
-use futures::{AsyncReadExt, StreamExt, TryStreamExt, future::join_all};
+use futures::{AsyncReadExt, TryStreamExt, future::join_all};
 use model::{
-    key::UserApiKey,
     memory::Memory,
     message::{ChatMessage, ChatMessageContent, Role},
-    upload::UserUpload,
+    upload::{IngestionStatus, UserUpload},
 };
-use mongodb::bson::{Bson, doc, oid::ObjectId};
-use redis_om::HashModel;
+use mongodb::bson::{self, Bson, doc, oid::ObjectId};
 use search::WebSearchOptions;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
 use crate::{
+    context::{
+        assistant,
+        auxiliary::{self, AuxiliaryTask},
+        instructions, keys, parameters, project, summary,
+        tokens::TokenEstimator,
+        window,
+    },
+    documents::retrieve,
     errors::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
     },
+    jobs::Job,
+    memories::{self, operations},
     middleware::auth::Auth,
-    payload::{
-        chat::{ChatMessageContentPayload, ChatMessagePayload},
-        memories::MemoryPayload,
-    },
-    state::{AppState, inference::InferenceProvider},
+    payload::chat::{ChatMessageContentPayload, ChatMessagePayload, CitationPayload},
+    state::AppState,
     streaming::{ApiDelta, ControlChunk},
+    temporary,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PromptCompletionPayload {
     pub message: String,
-    pub model: String,
+    /// Falls back to the chat's default model when omitted.
+    pub model: Option<String>,
     pub reasoning: Option<ReasoningEffort>,
     pub use_search: bool,
     pub use_memories: bool,
@@ -60,92 +67,164 @@ pub async fn handler(
     Auth(session): Auth,
     Json(payload): Json<PromptCompletionPayload>,
 ) -> Result<impl IntoResponse, ApplicationError> {
-    let model = state
-        .models()
-        .free_models()
-        .iter()
-        .find(|model| model.identifier == payload.model)
-        .cloned()
-        .or_else(|| {
-            state
-                .models()
-                .paid_models()
-                .iter()
-                .find(|model| model.identifier == payload.model)
-                .cloned()
-        })
-        .ok_or(ApplicationError::InvalidModelIdentifier)?;
-
     let chat = state
         .storage()
         .database()
         .chats
-        .get(doc! { "_id": chat_id, "user_id": session.user_id })
+        .get(doc! { "_id": chat_id, "user_id": session.user_id, "deleted_at": null })
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
-    let Some(chat) = chat else {
-        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
-            DatabaseError::ChatDoesNotExist,
-        )));
+    // temporary chats live in Redis, and nothing about them may be written to MongoDB
+    let (chat, temporary) = match chat {
+        Some(chat) => (chat, false),
+        None => {
+            let chat = temporary::get(&state, session.user_id, chat_id)
+                .await
+                .map_err(|e| {
+                    ApplicationError::StorageError(StorageError::DatabaseError(
+                        DatabaseError::Unknown(e),
+                    ))
+                })?;
+            let Some(chat) = chat else {
+                return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+                    DatabaseError::ChatDoesNotExist,
+                )));
+            };
+            (chat.chat(), true)
+        }
     };
 
-    // MESSAGES
-
-    let messages = state
+    let user = state
         .storage()
         .database()
-        .messages
-        .get_many_sorted(
-            doc! { "chat_id": chat.id.unwrap() },
-            doc! { "timestamp": -1 },
-        )
+        .users
+        .get_by_id(session.user_id)
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
+    let Some(user) = user else {
+        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::UserDoesNotExist,
+        )));
+    };
 
-    let mut messages = messages
-        .collect::<Vec<_>>()
-        .await
-        .into_iter()
-        .map(|msg| {
-            msg.map(|msg| OpenAIMessage {
-                content: msg
-                    .content
-                    .into_iter()
-                    .map(|content| match content {
-                        ChatMessageContent::Text { value } => {
-                            OpenAIMessageContent::Text { text: value }
+    let assistant = assistant::for_chat(&state, &chat, session.user_id).await?;
+    let settings = assistant::effective_settings(assistant.as_ref(), &chat.settings);
+    let tools = assistant
+        .as_ref()
+        .map(|assistant| assistant.tools)
+        .unwrap_or_default();
+    let use_search = payload.use_search || tools.search;
+    let use_memories = payload.use_memories || tools.memories;
+    let project = project::for_chat(&state, &chat).await?;
+    let memory_project_id = project::memory_project_id(project.as_ref());
+
+    let model = payload
+        .model
+        .as_ref()
+        .or(settings.default_model.as_ref())
+        .or(user.settings.default_model.as_ref())
+        .and_then(|identifier| state.models().find(identifier).cloned())
+        .ok_or(ApplicationError::InvalidModelIdentifier)?;
+    if !model.available {
+        return Err(ApplicationError::ModelUnavailable);
+    }
+
+    // MESSAGES
+
+    let history = if temporary {
+        temporary::messages(&state, chat_id).await.map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+    } else {
+        let messages = state
+            .storage()
+            .database()
+            .messages
+            .get_many_sorted(
+                doc! { "chat_id": chat.id.unwrap() },
+                doc! { "timestamp": 1 },
+            )
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+
+        messages
+            .try_collect::<Vec<ChatMessage>>()
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    anyhow!(e),
+                )))
+            })?
+    };
+
+    let mut messages = history
+        .iter()
+        .map(|msg| OpenAIMessage {
+            content: msg
+                .content
+                .iter()
+                .map(|content| match *content {
+                    ChatMessageContent::Text { ref value } => OpenAIMessageContent::Text {
+                        text: value.clone(),
+                    },
+                    ChatMessageContent::Image { id } if !model.capabilities.vision => {
+                        OpenAIMessageContent::Text {
+                            text: format!("**image file with id: {id}**"),
                         }
-                        ChatMessageContent::Image { id } => OpenAIMessageContent::ImageUrl {
-                            image_url: OpenAIMessageImageUrl {
-                                url: format!(
-                                    "https://t3-chat-clone.onrender.com/files/{}/{}",
-                                    chat_id.to_hex(),
-                                    id.to_hex()
-                                ),
-                            },
+                    }
+                    ChatMessageContent::Image { id } => OpenAIMessageContent::ImageUrl {
+                        image_url: OpenAIMessageImageUrl {
+                            url: format!(
+                                "https://t3-chat-clone.onrender.com/files/{}/{}",
+                                chat_id.to_hex(),
+                                id.to_hex()
+                            ),
                         },
-                        ChatMessageContent::Pdf { id } => OpenAIMessageContent::Text {
-                            text: format!("**pdf file with id: {id}**"),
-                        },
-                    })
-                    .collect(),
-                role: msg.role.to_string(),
-            })
+                    },
+                    ChatMessageContent::Pdf { id } => OpenAIMessageContent::Text {
+                        text: format!("**pdf file with id: {id}**"),
+                    },
+                })
+                .collect(),
+            role: msg.role.to_string(),
         })
-        .collect::<Result<Vec<_>, mongodb::error::Error>>()
-        .map_err(|e| {
-            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
-                anyhow!(e),
-            )))
-        })?;
+        .collect::<Vec<_>>();
+
+    if let Some(ref system_prompt) = settings.system_prompt {
+        messages.insert(
+            0,
+            OpenAIMessage {
+                role: Role::System.to_string(),
+                content: vec![OpenAIMessageContent::Text {
+                    text: system_prompt.clone(),
+                }],
+            },
+        );
+    }
+    if let Some(message) = project.as_ref().and_then(project::context_message) {
+        messages.insert(0, message);
+    }
+    messages.insert(
+        0,
+        instructions::user_context_message(&user.settings, Utc::now()),
+    );
+    let system_messages = messages
+        .iter()
+        .take_while(|message| message.role == Role::System.to_string())
+        .count();
 
     // FILES
 
-    let files_chat_id = if messages.is_empty() {
+    // uploads staged for a temporary chat could only have been staged without a chat
+    let files_chat_id = if history.is_empty() || temporary {
         None
     } else {
         Some(chat.id.unwrap())
@@ -165,6 +244,85 @@ pub async fn handler(
             anyhow!(e),
         )))
     })?;
+    // uploads are stored in MongoDB, so temporary chats take no attachments
+    if temporary && !files.is_empty() {
+        return Err(ApplicationError::TemporaryChatAttachments);
+    }
+
+    let images = files
+        .iter()
+        .filter(|file| file.content_type.starts_with("image/"))
+        .count();
+    let pdfs = files
+        .iter()
+        .filter(|file| file.content_type == "application/pdf")
+        .count();
+    if images > 0 && !model.capabilities.vision {
+        return Err(ApplicationError::ModelDoesNotSupportImages);
+    }
+    if pdfs > 0 && !model.capabilities.pdf {
+        return Err(ApplicationError::ModelDoesNotSupportPdf);
+    }
+
+    // assistant knowledge and project files go with every message; files the model cannot
+    // read are left out rather than failing every message
+    let mut knowledge = match assistant {
+        Some(ref assistant) => assistant::knowledge(&state, assistant).await?,
+        None => vec![],
+    };
+    if let Some(project_id) = project.as_ref().and_then(|project| project.id) {
+        knowledge.extend(project::files(&state, project_id).await?);
+    }
+    // indexed documents are retrieved from instead of being sent whole
+    let (indexed_knowledge, knowledge): (Vec<_>, Vec<_>) = knowledge
+        .into_iter()
+        .partition(|file| file.ingestion == Some(IngestionStatus::Indexed));
+    let knowledge = knowledge
+        .into_iter()
+        .filter(|file| {
+            if file.content_type.starts_with("image/") {
+                model.capabilities.vision
+            } else {
+                model.capabilities.pdf
+            }
+        })
+        .collect::<Vec<_>>();
+    let knowledge_images = knowledge
+        .iter()
+        .filter(|file| file.content_type.starts_with("image/"))
+        .count();
+
+    let mut document_ids = state
+        .storage()
+        .database()
+        .uploads
+        .get_many(doc! { "chat_id": chat.id.unwrap(), "ingestion": "indexed" })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .map_ok(|upload| upload.id)
+        .try_collect::<Vec<ObjectId>>()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow!(e),
+            )))
+        })?;
+    document_ids.extend(indexed_knowledge.iter().map(|file| file.id));
+
+    // older history is truncated to fit, but the new turn itself must fit on its own
+    let estimator = TokenEstimator::for_model(&model.identifier);
+    let prompt_budget = window::prompt_budget(&model);
+    let turn_tokens = estimator.messages(&messages[..system_messages])
+        + estimator.text(&payload.message)
+        + estimator.attachments(images, pdfs)
+        + estimator.attachments(knowledge_images, knowledge.len() - knowledge_images);
+    if turn_tokens > prompt_budget {
+        return Err(ApplicationError::ContextLengthExceeded);
+    }
+
+    let parameters = parameters::completion_parameters(&settings, &model, payload.reasoning);
 
     for file in files.iter() {
         state
@@ -185,64 +343,24 @@ pub async fn handler(
 
     // API KEY
 
-    let mut conn = state.storage().cache().connection();
-
-    let api_key = if let Ok(cached_key) =
-        UserApiKey::get(format!("openrouter-{}", session.user_id), &mut conn).await
-    {
-        Some(cached_key.key)
-    } else {
-        let key = state
-            .storage()
-            .database()
-            .keys
-            .get(doc! { "user_id": session.user_id })
-            .await
-            .unwrap();
-        if let Some(ref key) = key {
-            let _ = UserApiKey {
-                id: format!("openrouter-{}", session.user_id),
-                key: key.key.clone(),
-                key_id: key.id.unwrap().to_hex(),
-            }
-            .save(&mut conn)
-            .await;
-        }
-
-        key.map(|key| key.key)
-    };
+    let api_key = keys::openrouter_key(&state, session.user_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
 
     let client = if let Some(api_key) = api_key {
         // currently, only OpenRouter API keys are supported
         OpenAIClient::new(api_key, "https://openrouter.ai/api".to_string())
     } else {
-        match model.provider {
-            InferenceProvider::OpenRouter => state.inference().openrouter.clone(),
-            InferenceProvider::Chutes => state.inference().chutes.clone(),
-        }
+        state
+            .inference()
+            .client(model.provider)
+            .ok_or(ApplicationError::InferenceProviderNotConfigured)?
     };
 
     let (tx, rx) = flume::unbounded();
-
-    let memories = if payload.use_memories {
-        state
-            .storage()
-            .database()
-            .memories
-            .get_many(doc! { "user_id": session.user_id })
-            .await
-            .unwrap()
-            .map_ok(|memory| memory.content)
-            .try_collect::<Vec<String>>()
-            .await
-            .map_err(|e| {
-                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
-                    anyhow!(e),
-                )))
-            })?
-    } else {
-        vec![]
-    };
+    state.watch_chat(chat.id.unwrap(), &tx);
 
     let mut user_message_full_content = vec![ChatMessageContent::Text {
         value: payload.message.clone(),
@@ -264,65 +382,77 @@ pub async fn handler(
         chat_id: chat.id.unwrap(),
         updated_memory: None,
         timestamp: Utc::now(),
+        citations: vec![],
+        indexed: false,
     };
 
-    if messages.is_empty() {
-        let title_generation_message = format!(
-            "Here are some examples of first messages and their chat names:\n\ninput: I need help choosing a new laptop for college.\noutput: Laptop Recommendations for College\n\ninput:  Best places to eat Italian food in downtown Chicago?\noutput: Chicago Italian Food Guide\n\nNow, generate a descriptive name for a chat where the first message was: \"{}\"\nYour output must be a SINGLE, SHORT sentence. Do not include any parentheses, other symbols or any words except for the final result.",
-            payload.message
-        );
-
-        let task_state = Arc::clone(&state);
-        let task_tx = tx.clone();
-        tokio::spawn(async move {
-            let chat_name = task_state
-                .inference()
-                .chutes
-                .clone()
-                .prompt_completion_non_streaming(
-                    "zai-org/GLM-4.5-Air".to_string(),
-                    title_generation_message,
-                    Some(0.),
-                    Some(1000),
-                )
-                .await
-                .unwrap();
-            let chat_name = if chat_name.contains("</think>") {
-                chat_name.split_once("</think>").unwrap().1.to_string()
-            } else {
-                chat_name
-            };
-
-            let _ = task_tx
-                .send_async(ApiDelta::Control(ControlChunk::ChatNameUpdated {
-                    name: chat_name.clone(),
-                }))
-                .await;
-
-            task_state
-                .storage()
-                .database()
-                .chats
-                .update(chat.id.unwrap(), doc! { "$set": { "name": chat_name } })
-                .await
-                .unwrap();
-        });
+    let turn = history
+        .iter()
+        .filter(|message| matches!(message.role, Role::User))
+        .count()
+        + 1;
+    if !temporary && history.is_empty() && auxiliary::is_enabled(&state, AuxiliaryTask::Titles) {
+        let job = Job::GenerateTitle {
+            chat_id: chat.id.unwrap(),
+            first_message: payload.message.clone(),
+            model: model.identifier.clone(),
+        };
+        if let Err(e) = state.jobs().enqueue(job, Some(tx.clone())).await {
+            tracing::error!("Failed to enqueue chat name generation: {e}");
+        }
     }
 
-    let user_message_id = state
-        .storage()
-        .database()
-        .messages
-        .create(user_message.clone())
-        .await
-        .map_err(|e| {
-            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
-        })?;
+    let user_message_id = if temporary {
+        let id = ObjectId::new();
+        let message = ChatMessage {
+            id: Some(id),
+            ..user_message.clone()
+        };
+        temporary::push_message(&state, &message)
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+        id
+    } else {
+        let id = state
+            .storage()
+            .database()
+            .messages
+            .create(user_message.clone())
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+        state
+            .storage()
+            .database()
+            .chats
+            .update(
+                chat.id.unwrap(),
+                doc! {
+                    "$set": {
+                        "updated_at": bson::DateTime::from_chrono(user_message.timestamp),
+                    }
+                },
+            )
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+        id
+    };
 
     let stream_id = Uuid::new_v4();
     let task_state = Arc::clone(&state);
     tokio::spawn(async move {
-        let search_results = if payload.use_search {
+        let search_results = if use_search {
             let search_query = payload.message.trim();
             if search_query.is_empty() || search_query.len() > 400 {
                 None
@@ -347,14 +477,68 @@ pub async fn handler(
             .await
             .unwrap();
 
+        let retrieved = match retrieve::retrieve(
+            &task_state,
+            &document_ids,
+            &payload.message,
+            &estimator,
+        )
+        .await
+        {
+            Ok(retrieved) => retrieved,
+            Err(e) => {
+                tracing::error!("Failed to retrieve document chunks: {e}");
+                vec![]
+            }
+        };
+        let citations = retrieve::citations(&retrieved);
+        if !citations.is_empty() {
+            let _ = tx
+                .send_async(ApiDelta::Control(ControlChunk::DocumentsRetrieved {
+                    citations: citations
+                        .iter()
+                        .cloned()
+                        .map(CitationPayload::from)
+                        .collect(),
+                }))
+                .await;
+        }
+
+        // pinned memories plus the ones relevant to this message, so large collections still fit
+        let memories: Vec<Memory> = if use_memories {
+            match memories::relevant(
+                &task_state,
+                session.user_id,
+                project.as_ref(),
+                &payload.message,
+                &estimator,
+            )
+            .await
+            {
+                Ok(memories) => memories,
+                Err(e) => {
+                    tracing::error!("Failed to retrieve memories: {e}");
+                    vec![]
+                }
+            }
+        } else {
+            vec![]
+        };
+
         let mut user_message_content = user_message.content.clone();
-        let user_message_text = if payload.use_memories {
+        let user_message_text = if use_memories {
             format!(
                 "If necessary, you may use the following memories about the user to answer: {};\n{}",
                 if memories.is_empty() {
                     "No memories yet.".to_string()
                 } else {
-                    serde_json::to_string(&memories).unwrap()
+                    serde_json::to_string(
+                        &memories
+                            .iter()
+                            .map(|memory| &memory.content)
+                            .collect::<Vec<_>>(),
+                    )
+                    .unwrap()
                 },
                 match &user_message_content[0] {
                     ChatMessageContent::Text { value } => value,
@@ -407,6 +591,21 @@ pub async fn handler(
         } else {
             user_message_content
         };
+        // retrieved excerpts, knowledge and project files are sent with every turn, but never
+        // stored with the message
+        let message_for_assistant = message_for_assistant
+            .into_iter()
+            .chain((!retrieved.is_empty()).then(|| ChatMessageContent::Text {
+                value: retrieve::context_text(&retrieved),
+            }))
+            .chain(knowledge.iter().map(|file| {
+                if file.content_type.starts_with("image/") {
+                    ChatMessageContent::Image { id: file.id }
+                } else {
+                    ChatMessageContent::Pdf { id: file.id }
+                }
+            }))
+            .collect::<Vec<_>>();
 
         messages.push(OpenAIMessage {
             role: "user".to_string(),
@@ -483,13 +682,20 @@ pub async fn handler(
             updated_memory: None,
             chat_id: chat.id.unwrap(),
             timestamp: Utc::now(),
+            citations,
+            indexed: false,
         };
 
         let task2_state = Arc::clone(&task_state);
         let task_message = assistant_message.clone();
         let task_tx = tx.clone();
         let task_memories = memories.clone();
+        let task_model = model.identifier.clone();
         tokio::spawn(async move {
+            // temporary answers are stored once complete, and never renamed or mined for memories
+            if temporary {
+                return;
+            }
             task2_state
                 .storage()
                 .database()
@@ -498,102 +704,85 @@ pub async fn handler(
                 .await
                 .unwrap();
 
-            if !payload.use_memories {
-                return;
-            }
-            tokio::spawn(async move {
-                let prompt = format!("You are an AI Memory Assistant. Your task is to:
-1. Analyze the current user message.
-2. If there is an existing memory in [Existing memories] that directly pertains to the message, output NONE.
-3. If no relevant memory exists, but the message contains important information (e.g., goals, preferences, or facts), generate a new concise memory statement.
-4. If there are multiple memories you see in a single message, output the most important one.
-5. Otherwise, output NONE.
-
-Memory format (do NOT include braces): [Concise memory statement, single sentence]. Do not include any additional tokens in the output, except for the memory.
-For example: Input - Hey there! I am building an AI chat., Output - User is building an AI chat.
-
-Existing memories: {};
-
-Current user message: {:?}
-Your output:", if task_memories.is_empty() { "No memories yet".to_string() } else { serde_json::to_string(&task_memories).unwrap() }, payload.message.trim());
-
-                let memory = task2_state
-                    .inference()
-                    .chutes
-                    .clone()
-                    .prompt_completion_non_streaming(
-                        "zai-org/GLM-4.5-Air".to_string(),
-                        prompt,
-                        Some(0.3),
-                        Some(1000),
-                    )
-                    .await
-                    .unwrap();
-
-                let memory = if memory.contains("</think>") {
-                    memory.split_once("</think>").unwrap().1.to_string()
-                } else {
-                    memory
+            let retitle = task2_state
+                .inference()
+                .auxiliary
+                .retitle_turns
+                .is_some_and(|turns| turn % turns == 0);
+            if retitle && auxiliary::is_enabled(&task2_state, AuxiliaryTask::Titles) {
+                let job = Job::RetitleChat {
+                    chat_id: chat.id.unwrap(),
+                    turn,
+                    model: task_model.clone(),
                 };
-
-                if memory.trim().starts_with("NONE") {
-                    return;
+                if let Err(e) = task2_state.jobs().enqueue(job, None).await {
+                    tracing::error!("Failed to enqueue chat retitle: {e}");
                 }
+            }
 
-                let memory = if memory.starts_with('[') {
-                    &memory[1..]
-                } else {
-                    &memory
-                };
-                let memory = if memory.ends_with(']') {
-                    &memory[..(memory.len() - 1)]
-                } else if memory.ends_with("].") {
-                    &format!("{}.", &memory[..(memory.len() - 2)])
-                } else {
-                    memory
-                };
-
-                let memory = memory.to_string();
-
-                let memory_id = task2_state
-                    .storage()
-                    .database()
-                    .memories
-                    .create(Memory {
-                        id: None,
-                        user_id: session.user_id,
-                        content: memory.clone(),
-                    })
-                    .await
-                    .unwrap();
-                task2_state
-                    .storage()
-                    .database()
-                    .messages
-                    .update(
-                        assistant_message_id,
-                        doc! { "$set": { "updated_memory": memory.clone() } },
-                    )
-                    .await
-                    .unwrap();
-
-                let _ = task_tx
-                    .send_async(ApiDelta::Control(ControlChunk::MemoryAdded {
-                        memory: MemoryPayload {
-                            id: memory_id,
-                            content: memory,
-                        },
-                    }))
-                    .await;
-            });
+            if !use_memories || !auxiliary::is_enabled(&task2_state, AuxiliaryTask::Memories) {
+                return;
+            }
+            let job = Job::ExtractMemories {
+                target: operations::Target {
+                    user_id: session.user_id,
+                    project_id: memory_project_id,
+                    source_message_id: user_message_id,
+                },
+                assistant_message_id,
+                memory_ids: task_memories
+                    .iter()
+                    .filter_map(|memory| memory.id)
+                    .collect(),
+                message: payload.message,
+                model: task_model,
+            };
+            if let Err(e) = task2_state.jobs().enqueue(job, Some(task_tx)).await {
+                tracing::error!("Failed to enqueue memory extraction: {e}");
+            }
         });
 
+        if let Some(ref summary) = chat.summary {
+            messages.insert(system_messages, summary::context_message(summary));
+        }
+        let mut context = window::fit(messages, prompt_budget, &estimator);
+        if context.dropped == 0 && chat.summary.is_some() {
+            // nothing was cut, so the full history makes the summary redundant
+            context.messages.remove(system_messages);
+        }
+        if !temporary
+            && summary::is_stale(chat.summary.as_ref(), &history[..context.dropped])
+            && auxiliary::is_enabled(&task_state, AuxiliaryTask::Summaries)
+        {
+            let job = Job::UpdateSummary {
+                chat_id: chat.id.unwrap(),
+                dropped: history[..context.dropped]
+                    .iter()
+                    .filter_map(|message| message.id)
+                    .collect(),
+                model: model.identifier.clone(),
+            };
+            if let Err(e) = task_state.jobs().enqueue(job, None).await {
+                tracing::error!("Failed to enqueue chat summary update: {e}");
+            }
+        }
+        if context.dropped > 0 {
+            tracing::debug!(
+                "Dropped {} messages to fit the context window.",
+                context.dropped
+            );
+            let _ = tx
+                .send_async(ApiDelta::Control(ControlChunk::ContextTruncated {
+                    dropped_messages: context.dropped,
+                }))
+                .await;
+        }
+
         let stream = client
             .completion(
-                payload.model,
-                messages,
-                Some(0.7),
-                payload.reasoning,
+                model.identifier.clone(),
+                context.messages,
+                parameters,
                 vec![OpenRouterRequestPlugin {
                     id: "file-parser".to_string(),
                     pdf: OpenRouterRequestPdfPlugin {
@@ -626,7 +815,7 @@ Your output:", if task_memories.is_empty() { "No memories yet".to_string() } els
 
         while let Ok(chunk) = stream.try_next().await {
             let Some(chunk) = chunk else { break };
-            let delta = &chunk.choices.get(0).unwrap().delta;
+            let delta = &chunk.choices.first().unwrap().delta;
             let reasoning_content = delta.reasoning.as_ref();
             let delta_content = delta.content.as_ref();
 
@@ -642,10 +831,10 @@ Your output:", if task_memories.is_empty() { "No memories yet".to_string() } els
                     reasoning = Some(reasoning_content.to_string())
                 }
 
-                if reasoning_acc.is_none() {
-                    reasoning_acc = Some(reasoning_content.to_string());
+                if let Some(ref mut reasoning_acc) = reasoning_acc {
+                    reasoning_acc.push_str(reasoning_content);
                 } else {
-                    reasoning_acc.as_mut().unwrap().push_str(reasoning_content);
+                    reasoning_acc = Some(reasoning_content.to_string());
                 }
             }
 
@@ -672,24 +861,55 @@ Your output:", if task_memories.is_empty() { "No memories yet".to_string() } els
         }
         tracing::debug!("Sending done chunk");
         let assistant_message_content = vec![ChatMessageContent::Text { value: content }];
+        let assistant_message = ChatMessage {
+            content: assistant_message_content.clone(),
+            reasoning: reasoning.clone(),
+            ..assistant_message
+        };
         tx.send(ApiDelta::Control(ControlChunk::Done {
-            message: ChatMessage {
-                content: assistant_message_content.clone(),
-                reasoning: reasoning.clone(),
-                ..assistant_message
-            },
+            message: assistant_message.clone(),
         }))
         .unwrap();
-        task_state
-            .storage()
-            .database()
-            .messages
-            .update(
-                assistant_message_id,
-                doc! { "$set": { "content": assistant_message_content, "reasoning": reasoning } },
-            )
-            .await
-            .unwrap();
+        if temporary {
+            if let Err(e) = temporary::push_message(&task_state, &assistant_message).await {
+                tracing::error!("Failed to store the answer in a temporary chat: {e}");
+            }
+        } else {
+            task_state
+                .storage()
+                .database()
+                .messages
+                .update(
+                    assistant_message_id,
+                    doc! {
+                        "$set": {
+                            "content": assistant_message_content,
+                            "reasoning": reasoning,
+                        }
+                    },
+                )
+                .await
+                .unwrap();
+            let result = task_state
+                .storage()
+                .database()
+                .chats
+                .update(
+                    chat.id.unwrap(),
+                    doc! { "$set": { "updated_at": bson::DateTime::now() } },
+                )
+                .await;
+            if let Err(e) = result {
+                tracing::error!("Failed to update the chat's activity: {e}");
+            }
+            let job = Job::IndexMessages {
+                user_id: session.user_id,
+                message_ids: vec![user_message_id, assistant_message_id],
+            };
+            if let Err(e) = task_state.jobs().enqueue(job, None).await {
+                tracing::error!("Failed to enqueue message indexing: {e}");
+            }
+        }
         tokio::spawn(async move {
             tokio::time::sleep(Duration::from_secs(20)).await;
             if task_state.remove_stream(&stream_id) {
@@ -721,7 +941,8 @@ Your output:", if task_memories.is_empty() { "No memories yet".to_string() } els
             reasoning: None,
             updated_memory: None,
             role: user_message.role,
-            timestamp: user_message.timestamp
+            timestamp: user_message.timestamp,
+            citations: vec![]
           }
         })),
     )
@@ -1,40 +1,54 @@
-use anyhow::anyhow;
 use axum::{
     Json,
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
-use futures::{StreamExt, TryStreamExt};
-use model::{message::ChatMessageContent, share::Share};
+use futures::TryStreamExt;
+use model::{message::ChatMessage, share::Share};
 use mongodb::bson::{doc, oid::ObjectId};
 use redis_om::HashModel;
 use serde::Deserialize;
 use std::sync::Arc;
+use validator::Validate;
 
 use crate::{
+    data::cursor::Cursor,
     errors::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
     },
     middleware::auth::Auth,
-    payload::chat::{ChatMessageContentPayload, ChatMessagePayload},
+    payload::chat::{ChatMessagePayload, ChatMessagesPayload},
     state::AppState,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct ListChatMessagesPayload {
-    pub start: usize,
-    pub take: usize,
+    /// Lists the messages older than this cursor.
+    pub before: Option<String>,
+    /// Lists the messages newer than this cursor.
+    pub after: Option<String>,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be 1-100."))]
+    pub limit: usize,
     pub share_id: Option<ObjectId>,
 }
 
+fn default_limit() -> usize {
+    30
+}
+
 pub async fn handler(
     State(state): State<Arc<AppState>>,
     Auth(session): Auth,
     Path(chat_id): Path<ObjectId>,
     Query(payload): Query<ListChatMessagesPayload>,
 ) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
     let chat = if let Some(id) = payload.share_id {
         let mut conn = state.storage().cache().connection();
         let share = Share::get(chat_id.to_hex(), &mut conn).await.unwrap();
@@ -45,7 +59,7 @@ pub async fn handler(
             .storage()
             .database()
             .chats
-            .get(doc! { "_id": chat_id })
+            .get(doc! { "_id": chat_id, "deleted_at": null })
             .await
     } else {
         state
@@ -65,51 +79,77 @@ pub async fn handler(
         )));
     };
 
-    let messages = state
+    let before = payload.before.as_deref().map(Cursor::decode);
+    let after = payload.after.as_deref().map(Cursor::decode);
+    let (cursor, newer) = match (before, after) {
+        (None, None) => (None, false),
+        (Some(Some(cursor)), None) => (Some(cursor), false),
+        (None, Some(Some(cursor))) => (Some(cursor), true),
+        _ => return Err(ApplicationError::InvalidCursor),
+    };
+
+    let mut filter = doc! { "chat_id": chat.id.unwrap() };
+    if let Some(ref cursor) = cursor {
+        filter.extend(if newer {
+            cursor.newer("timestamp")
+        } else {
+            cursor.older("timestamp")
+        });
+    }
+    let order = if newer { 1 } else { -1 };
+
+    let mut messages: Vec<ChatMessage> = state
         .storage()
         .database()
         .messages
-        .get_many_sorted(
-            doc! { "chat_id": chat.id.unwrap() },
-            doc! { "timestamp": -1 },
-        )
+        .collection()
+        .find(filter)
+        .sort(doc! { "timestamp": order, "_id": order })
+        .limit(payload.limit as i64 + 1)
         .await
         .map_err(|e| {
-            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
-        })?;
-
-    let messages = messages
-        .skip(payload.start)
-        .take(payload.take)
-        .map(|message| {
-            message.map(|msg| ChatMessagePayload {
-                id: msg.id.unwrap(),
-                content: msg
-                    .content
-                    .into_iter()
-                    .map(|message| match message {
-                        ChatMessageContent::Text { value } => {
-                            ChatMessageContentPayload::Text { value }
-                        }
-                        ChatMessageContent::Image { id } => ChatMessageContentPayload::Image { id },
-                        ChatMessageContent::Pdf { id } => ChatMessageContentPayload::Pdf { id },
-                    })
-                    .collect(),
-                model: msg.model,
-                timestamp: chat.timestamp,
-                reasoning: msg.reasoning,
-                updated_memory: msg.updated_memory,
-                chat_id: msg.chat_id,
-                role: msg.role,
-            })
-        })
-        .try_collect::<Vec<_>>()
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow::anyhow!(e),
+            )))
+        })?
+        .try_collect()
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
-                anyhow!(e),
+                anyhow::anyhow!(e),
             )))
         })?;
 
-    Ok((StatusCode::OK, Json(messages)).into_response())
+    let more = messages.len() > payload.limit;
+    messages.truncate(payload.limit);
+    if newer {
+        messages.reverse();
+    }
+
+    // the window is bounded on the side it was fetched towards only when more messages remain,
+    // and on the other side only when it started from a cursor
+    let oldest = messages.last().map(cursor_of).or(cursor);
+    let newest = messages.first().map(cursor_of).or(cursor);
+    let (before, after) = if newer {
+        (oldest, newest.filter(|_| more))
+    } else {
+        (oldest.filter(|_| more), newest.filter(|_| cursor.is_some()))
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(ChatMessagesPayload {
+            messages: messages.into_iter().map(ChatMessagePayload::from).collect(),
+            before: before.map(|cursor| cursor.encode()),
+            after: after.map(|cursor| cursor.encode()),
+        }),
+    )
+        .into_response())
+}
+
+fn cursor_of(message: &ChatMessage) -> Cursor {
+    Cursor {
+        timestamp: message.timestamp,
+        id: message.id.unwrap(),
+    }
 }
@@ -2,30 +2,63 @@ use std::sync::Arc;
 
 use axum::{
     Router,
-    routing::{delete as method_delete, get, post},
+    routing::{delete as method_delete, get, post, put},
 };
 
-use crate::state::AppState;
+use model::chat::Chat;
+use mongodb::bson::oid::ObjectId;
 
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    state::AppState,
+};
+
+pub mod archive;
 pub mod create;
+pub mod create_temporary;
 pub mod delete;
+pub mod delete_temporary;
 pub mod list;
 pub mod message;
 pub mod messages;
+pub mod move_to_folder;
+pub mod move_to_project;
+pub mod pin;
+pub mod regenerate_title;
 pub mod rename;
+pub mod restore;
+pub mod settings;
 pub mod share;
 pub mod share_state;
 pub mod state;
+pub mod summary;
+pub mod temporary;
+pub mod trash;
 pub mod unshare;
+pub mod update_settings;
+pub mod update_summary;
+pub mod update_tags;
 
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/chats", post(create::handler))
         .route("/chats", get(list::handler))
+        .route("/chats/trash", get(trash::handler))
+        .route("/chats/temporary", post(create_temporary::handler))
+        .route("/chats/temporary/{chat_id}", get(temporary::handler))
+        .route(
+            "/chats/temporary/{chat_id}",
+            method_delete(delete_temporary::handler),
+        )
+        .route("/chats/{chat_id}/restore", post(restore::handler))
         .route("/chats/{chat_id}/message", post(message::handler))
         .route("/chats/{chat_id}/messages", get(messages::handler))
         .route("/chats/{chat_id}", method_delete(delete::handler))
         .route("/chats/{chat_id}/rename", post(rename::handler))
+        .route("/chats/{chat_id}/title", post(regenerate_title::handler))
         .route("/chats/{chat_id}/share", post(share::handler))
         .route("/chats/{chat_id}/share", get(share_state::handler))
         .route(
@@ -33,4 +66,42 @@ pub fn router() -> Router<Arc<AppState>> {
             method_delete(unshare::handler),
         )
         .route("/chats/{chat_id}", get(state::handler))
+        .route("/chats/{chat_id}/summary", get(summary::handler))
+        .route("/chats/{chat_id}/summary", put(update_summary::handler))
+        .route("/chats/{chat_id}/settings", get(settings::handler))
+        .route("/chats/{chat_id}/settings", put(update_settings::handler))
+        .route("/chats/{chat_id}/project", put(move_to_project::handler))
+        .route("/chats/{chat_id}/folder", put(move_to_folder::handler))
+        .route("/chats/{chat_id}/pin", put(pin::handler))
+        .route("/chats/{chat_id}/archive", put(archive::handler))
+        .route("/chats/{chat_id}/tags", put(update_tags::handler))
+}
+
+/// Loads a chat of the user. Chats in the trash are treated as missing until they are restored.
+pub async fn load_owned(
+    state: &AppState,
+    chat_id: ObjectId,
+    user_id: ObjectId,
+) -> Result<Chat, ApplicationError> {
+    let chat = state
+        .storage()
+        .database()
+        .chats
+        .get_by_id(chat_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .filter(|chat| chat.deleted_at.is_none())
+        .ok_or(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::ChatDoesNotExist,
+        )))?;
+
+    if chat.user_id != user_id {
+        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::ChatDoesNotBelongToUser,
+        )));
+    }
+
+    Ok(chat)
 }
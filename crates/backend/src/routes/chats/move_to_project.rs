@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::{
+    context::project,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    routes::chats,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct MoveChatPayload {
+    /// Removes the chat from its project when empty.
+    pub project_id: Option<ObjectId>,
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+    Json(payload): Json<MoveChatPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    if let Some(project_id) = payload.project_id {
+        project::load_owned(&state, project_id, session.user_id).await?;
+    }
+
+    state
+        .storage()
+        .database()
+        .chats
+        .update(
+            chat_id,
+            doc! { "$set": { "project_id": payload.project_id } },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok(StatusCode::OK.into_response())
+}
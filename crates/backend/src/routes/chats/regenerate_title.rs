@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use futures::TryStreamExt;
+use model::message::ChatMessage;
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::{
+    context::{
+        auxiliary::{self, AuxiliaryTask},
+        title,
+    },
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    routes::chats,
+    state::AppState,
+    streaming::ControlChunk,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    let messages: Vec<ChatMessage> = state
+        .storage()
+        .database()
+        .messages
+        .collection()
+        .find(doc! { "chat_id": chat_id })
+        .sort(doc! { "timestamp": 1 })
+        .limit(title::TITLE_CONTEXT_MESSAGES)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                e.into(),
+            )))
+        })?
+        .try_collect()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                e.into(),
+            )))
+        })?;
+    if messages.is_empty() {
+        return Err(ApplicationError::ChatHasNoMessages);
+    }
+
+    let user = state
+        .storage()
+        .database()
+        .users
+        .get_by_id(session.user_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    let model = chat
+        .settings
+        .default_model
+        .or(user.and_then(|user| user.settings.default_model))
+        .unwrap_or_default();
+    let auxiliary = auxiliary::resolve(&state, AuxiliaryTask::Titles, session.user_id, &model)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .ok_or(ApplicationError::AuxiliaryModelUnavailable)?;
+
+    let name = title::from_messages(&auxiliary, &messages)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to regenerate chat name: {e:#}");
+            ApplicationError::TitleGenerationFailed
+        })?;
+
+    state
+        .storage()
+        .database()
+        .chats
+        .update(
+            chat_id,
+            doc! { "$set": { "name": &name, "renamed": false } },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    state.notify_chat(
+        chat_id,
+        ControlChunk::ChatNameUpdated { name: name.clone() },
+    );
+
+    Ok((StatusCode::OK, Json(json!({ "name": name }))).into_response())
+}
@@ -15,6 +15,7 @@ use crate::{
         storage::{StorageError, database::DatabaseError},
     },
     middleware::auth::Auth,
+    routes::chats,
     state::AppState,
 };
 
@@ -36,33 +37,16 @@ pub async fn handler(
         name
     };
 
-    let chat = state
-        .storage()
-        .database()
-        .chats
-        .get_by_id(chat_id)
-        .await
-        .map_err(|e| {
-            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
-        })?;
-
-    let Some(chat) = chat else {
-        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
-            DatabaseError::ChatDoesNotExist,
-        )));
-    };
-
-    if chat.user_id != session.user_id {
-        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
-            DatabaseError::ChatDoesNotBelongToUser,
-        )));
-    }
+    let chat = chats::load_owned(&state, chat_id, session.user_id).await?;
 
     state
         .storage()
         .database()
         .chats
-        .update(chat.id.unwrap(), doc! { "$set": { "name": name } })
+        .update(
+            chat.id.unwrap(),
+            doc! { "$set": { "name": name, "renamed": true } },
+        )
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
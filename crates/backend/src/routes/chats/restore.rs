@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::chat::Chat;
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::chat::ChatPayload,
+    state::AppState,
+};
+
+/// Takes the chat out of the trash.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = state
+        .storage()
+        .database()
+        .chats
+        .get_by_id(chat_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    let Some(chat) = chat else {
+        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::ChatDoesNotExist,
+        )));
+    };
+
+    if chat.user_id != session.user_id {
+        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::ChatDoesNotBelongToUser,
+        )));
+    }
+
+    state
+        .storage()
+        .database()
+        .chats
+        .update(chat_id, doc! { "$set": { "deleted_at": null } })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ChatPayload::from(Chat {
+            deleted_at: None,
+            ..chat
+        })),
+    )
+        .into_response())
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+
+use crate::{
+    errors::ApplicationError, middleware::auth::Auth, payload::chat::ChatSettingsPayload,
+    routes::chats, state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ChatSettingsPayload::from(chat.settings)),
+    )
+        .into_response())
+}
@@ -14,9 +14,10 @@ use serde_json::json;
 use crate::{
     errors::{
         ApplicationError,
-        storage::{StorageError, cache::CacheError, database::DatabaseError},
+        storage::{StorageError, cache::CacheError},
     },
     middleware::auth::Auth,
+    routes::chats,
     state::AppState,
 };
 
@@ -25,27 +26,7 @@ pub async fn handler(
     Auth(session): Auth,
     Path(chat_id): Path<ObjectId>,
 ) -> Result<impl IntoResponse, ApplicationError> {
-    let chat = state
-        .storage()
-        .database()
-        .chats
-        .get_by_id(chat_id)
-        .await
-        .map_err(|e| {
-            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
-        })?;
-
-    let Some(chat) = chat else {
-        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
-            DatabaseError::ChatDoesNotExist,
-        )));
-    };
-
-    if chat.user_id != session.user_id {
-        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
-            DatabaseError::ChatDoesNotBelongToUser,
-        )));
-    }
+    chats::load_owned(&state, chat_id, session.user_id).await?;
 
     let mut conn = state.storage().cache().connection();
 
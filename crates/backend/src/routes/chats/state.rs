@@ -48,7 +48,7 @@ pub async fn handler(
             .storage()
             .database()
             .chats
-            .get(doc! { "_id": chat_id })
+            .get(doc! { "_id": chat_id, "deleted_at": null })
             .await
     } else {
         state
@@ -68,14 +68,5 @@ pub async fn handler(
         )));
     };
 
-    Ok((
-        StatusCode::OK,
-        Json(ChatPayload {
-            id: chat.id.unwrap(),
-            name: chat.name,
-            user_id: chat.user_id,
-            timestamp: chat.timestamp,
-        }),
-    )
-        .into_response())
+    Ok((StatusCode::OK, Json(ChatPayload::from(chat))).into_response())
 }
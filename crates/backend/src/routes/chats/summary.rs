@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+
+use crate::{
+    errors::ApplicationError, middleware::auth::Auth, payload::chat::ChatSummaryPayload,
+    routes::chats, state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    let summary = chat.summary.map(|summary| ChatSummaryPayload {
+        content: summary.content,
+        covers_until: summary.covers_until,
+        updated_at: summary.updated_at,
+    });
+
+    Ok((StatusCode::OK, Json(summary)).into_response())
+}
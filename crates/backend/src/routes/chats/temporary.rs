@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::chat::{ChatMessagePayload, TemporaryChatPayload},
+    state::AppState,
+    temporary,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = temporary::get(&state, session.user_id, chat_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    let Some(chat) = chat else {
+        return Err(ApplicationError::StorageError(StorageError::DatabaseError(
+            DatabaseError::ChatDoesNotExist,
+        )));
+    };
+
+    let messages = temporary::messages(&state, chat_id).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(TemporaryChatPayload {
+            id: chat.id,
+            timestamp: chat.timestamp,
+            ttl: state.temporary_chat_ttl().as_secs(),
+            messages: messages.into_iter().map(ChatMessagePayload::from).collect(),
+        }),
+    )
+        .into_response())
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use futures::TryStreamExt;
+use model::chat::Chat;
+use mongodb::bson::doc;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    data::cursor::Cursor,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::chat::{ChatListPayload, ChatPayload},
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListTrashPayload {
+    pub cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be 1-100."))]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    30
+}
+
+/// Lists the user's trashed chats, most recently deleted first.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Query(payload): Query<ListTrashPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let mut filter = doc! {
+        "user_id": session.user_id,
+        "deleted_at": { "$ne": null },
+    };
+    if let Some(ref cursor) = payload.cursor {
+        let cursor = Cursor::decode(cursor).ok_or(ApplicationError::InvalidCursor)?;
+        filter.insert("$and", vec![cursor.older("deleted_at")]);
+    }
+
+    let mut chats: Vec<Chat> = state
+        .storage()
+        .database()
+        .chats
+        .collection()
+        .find(filter)
+        .sort(doc! { "deleted_at": -1, "_id": -1 })
+        .limit(payload.limit as i64 + 1)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow::anyhow!(e),
+            )))
+        })?
+        .try_collect()
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow::anyhow!(e),
+            )))
+        })?;
+
+    let next_cursor = if chats.len() > payload.limit {
+        chats.truncate(payload.limit);
+        chats.last().map(|chat| {
+            Cursor {
+                timestamp: chat.deleted_at.unwrap(),
+                id: chat.id.unwrap(),
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(ChatListPayload {
+            chats: chats.into_iter().map(ChatPayload::from).collect(),
+            next_cursor,
+        }),
+    )
+        .into_response())
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::chat::{ChatSettings, ReasoningEffort};
+use mongodb::bson::{self, doc, oid::ObjectId};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::chat::ChatSettingsPayload,
+    routes::chats,
+    state::AppState,
+};
+
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateChatSettingsPayload {
+    #[validate(length(
+        max = 20000,
+        message = "System prompt must be at most 20000 chars long."
+    ))]
+    pub system_prompt: Option<String>,
+    #[validate(range(min = 0.0, max = 2.0, message = "Temperature must be between 0 and 2."))]
+    pub temperature: Option<f32>,
+    #[validate(range(min = 0.0, max = 1.0, message = "Top P must be between 0 and 1."))]
+    pub top_p: Option<f32>,
+    #[validate(range(min = 1, message = "Max tokens must be positive."))]
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    #[validate(length(max = 4, message = "At most 4 stop sequences are allowed."))]
+    pub stop: Vec<String>,
+    pub seed: Option<i64>,
+    pub default_model: Option<String>,
+    pub default_reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl UpdateChatSettingsPayload {
+    pub fn into_settings(self) -> ChatSettings {
+        ChatSettings {
+            system_prompt: self
+                .system_prompt
+                .map(|prompt| prompt.trim().to_string())
+                .filter(|prompt| !prompt.is_empty()),
+            temperature: self.temperature,
+            top_p: self.top_p,
+            max_tokens: self.max_tokens,
+            stop: self
+                .stop
+                .into_iter()
+                .filter(|stop| !stop.is_empty())
+                .collect(),
+            seed: self.seed,
+            default_model: self.default_model,
+            default_reasoning_effort: self.default_reasoning_effort,
+        }
+    }
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+    Json(payload): Json<UpdateChatSettingsPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    if payload
+        .default_model
+        .as_ref()
+        .is_some_and(|identifier| state.models().find(identifier).is_none())
+    {
+        return Err(ApplicationError::InvalidModelIdentifier);
+    }
+
+    chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    let settings = payload.into_settings();
+    let settings_bson = bson::to_bson(&settings).map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            anyhow::anyhow!(e),
+        )))
+    })?;
+
+    state
+        .storage()
+        .database()
+        .chats
+        .update(chat_id, doc! { "$set": { "settings": settings_bson } })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok((StatusCode::OK, Json(ChatSettingsPayload::from(settings))).into_response())
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use model::chat::ChatSummary;
+use mongodb::bson::{self, doc, oid::ObjectId};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::chat::ChatSummaryPayload,
+    routes::chats,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateChatSummaryPayload {
+    pub content: String,
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+    Json(payload): Json<UpdateChatSummaryPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let chat = chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    let Some(summary) = chat.summary else {
+        return Err(ApplicationError::ChatHasNoSummary);
+    };
+
+    let content = payload.content.trim().to_string();
+    // clearing the summary lets it be regenerated from scratch
+    let summary = (!content.is_empty()).then(|| ChatSummary {
+        content,
+        updated_at: Utc::now(),
+        ..summary
+    });
+    let summary_bson = bson::to_bson(&summary).map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            anyhow::anyhow!(e),
+        )))
+    })?;
+
+    state
+        .storage()
+        .database()
+        .chats
+        .update(chat_id, doc! { "$set": { "summary": summary_bson } })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    let summary = summary.map(|summary| ChatSummaryPayload {
+        content: summary.content,
+        covers_until: summary.covers_until,
+        updated_at: summary.updated_at,
+    });
+
+    Ok((StatusCode::OK, Json(summary)).into_response())
+}
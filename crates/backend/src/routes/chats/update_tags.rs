@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    data::tags,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    routes::chats,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateChatTagsPayload {
+    #[validate(
+        length(max = 10, message = "A chat can have at most 10 tags."),
+        custom(function = "tags::validate")
+    )]
+    pub tags: Vec<String>,
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(chat_id): Path<ObjectId>,
+    Json(payload): Json<UpdateChatTagsPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    chats::load_owned(&state, chat_id, session.user_id).await?;
+
+    state
+        .storage()
+        .database()
+        .chats
+        .update(
+            chat_id,
+            doc! { "$set": { "tags": tags::normalize(&payload.tags) } },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok(StatusCode::OK.into_response())
+}
@@ -11,7 +11,7 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::{
-    payload::chat::{ChatMessageContentPayload, ChatMessagePayload},
+    payload::chat::{ChatMessageContentPayload, ChatMessagePayload, CitationPayload},
     state::AppState,
     streaming::{ApiDelta, ControlChunk},
 };
@@ -48,7 +48,8 @@ pub async fn handler(
                       reasoning: message.reasoning,
                       role: message.role,
                       updated_memory: message.updated_memory,
-                      timestamp: message.timestamp
+                      timestamp: message.timestamp,
+                      citations: message.citations.into_iter().map(CitationPayload::from).collect()
                 } } }),
                 )
             }
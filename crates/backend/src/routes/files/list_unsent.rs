@@ -41,6 +41,7 @@ pub async fn handler(
             chat_id: upload.chat_id,
             user_id: upload.user_id,
             content_type: upload.content_type,
+            ingestion: upload.ingestion,
         })
         .try_collect::<Vec<UserUploadPayload>>()
         .await
@@ -73,6 +74,7 @@ pub async fn no_chat_id_handler(
             chat_id: upload.chat_id,
             user_id: upload.user_id,
             content_type: upload.content_type,
+            ingestion: upload.ingestion,
         })
         .try_collect::<Vec<UserUploadPayload>>()
         .await
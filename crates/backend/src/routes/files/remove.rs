@@ -9,6 +9,7 @@ use mongodb::bson::{Bson, doc, oid::ObjectId};
 use reqwest::StatusCode;
 
 use crate::{
+    documents,
     errors::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
@@ -56,6 +57,9 @@ pub async fn handler(
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
+    documents::remove(&state, upload.id).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+    })?;
 
     Ok((StatusCode::OK).into_response())
 }
@@ -99,6 +103,9 @@ pub async fn no_chat_id_handler(
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
+    documents::remove(&state, upload.id).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+    })?;
 
     Ok((StatusCode::OK).into_response())
 }
@@ -7,13 +7,14 @@ use axum::{
     response::IntoResponse,
 };
 use futures::{AsyncWriteExt, StreamExt, TryStreamExt};
-use model::upload::UserUpload;
+use model::upload::{IngestionStatus, UserUpload};
 use mongodb::bson::oid::ObjectId;
 use reqwest::StatusCode;
 use tokio::io::AsyncReadExt;
 use tokio_util::io::StreamReader;
 
 use crate::{
+    documents,
     errors::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
@@ -21,6 +22,7 @@ use crate::{
     middleware::auth::Auth,
     payload::upload::UserUploadPayload,
     state::AppState,
+    temporary,
 };
 
 pub async fn handler(
@@ -43,6 +45,17 @@ pub async fn handler(
                 )))
             })?;
         let Some(chat) = chat else {
+            // uploads are stored in MongoDB, which temporary chats must never be written to
+            let temporary = temporary::get(&state, session.user_id, chat_id)
+                .await
+                .map_err(|e| {
+                    ApplicationError::StorageError(StorageError::DatabaseError(
+                        DatabaseError::Unknown(e),
+                    ))
+                })?;
+            if temporary.is_some() {
+                return Err(ApplicationError::TemporaryChatAttachments);
+            }
             return Err(ApplicationError::StorageError(StorageError::DatabaseError(
                 DatabaseError::ChatDoesNotExist,
             )));
@@ -88,10 +101,8 @@ pub async fn handler(
 
     let file_stream = file.into_stream();
 
-    let mut reader = StreamReader::new(
-        file_stream
-            .map(|result| result.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))),
-    );
+    let mut reader =
+        StreamReader::new(file_stream.map(|result| result.map_err(std::io::Error::other)));
 
     let mut size = 0;
     // Buffer to hold chunks
@@ -131,21 +142,28 @@ pub async fn handler(
 
     let attachment_id = stream.id().as_object_id().unwrap();
 
+    let upload = UserUpload {
+        id: attachment_id,
+        chat_id,
+        user_id: session.user_id,
+        content_type: content_type.to_string(),
+        is_sent: false,
+        assistant_id: None,
+        project_id: None,
+        ingestion: documents::is_indexable(&content_type).then_some(IngestionStatus::Pending),
+    };
     state
         .storage()
         .database()
         .uploads
-        .create(UserUpload {
-            id: attachment_id,
-            chat_id: chat_id,
-            user_id: session.user_id,
-            content_type: content_type.to_string(),
-            is_sent: false,
-        })
+        .create(upload.clone())
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
+    if upload.ingestion.is_some() {
+        documents::spawn_ingestion(Arc::clone(&state), upload.clone());
+    }
 
     // created UserUpload
 
@@ -155,6 +173,7 @@ pub async fn handler(
             id: attachment_id,
             chat_id,
             content_type,
+            ingestion: upload.ingestion,
             user_id: session.user_id,
         }),
     )
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use model::folder::Folder;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::folders::FolderPayload,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct FolderBody {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1-64 chars long."))]
+    pub name: String,
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Json(payload): Json<FolderBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let mut folder = Folder {
+        id: None,
+        user_id: session.user_id,
+        name: payload.name.trim().to_string(),
+        timestamp: Utc::now(),
+    };
+
+    let id = state
+        .storage()
+        .database()
+        .folders
+        .create(folder.clone())
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    folder.id = Some(id);
+
+    Ok((StatusCode::OK, Json(FolderPayload::from(folder))).into_response())
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    routes::folders,
+    state::AppState,
+};
+
+/// Deletes the folder. Its chats are kept and become unfiled.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(folder_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    folders::load_owned(&state, folder_id, session.user_id).await?;
+
+    state
+        .storage()
+        .database()
+        .folders
+        .delete(folder_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    state
+        .storage()
+        .database()
+        .chats
+        .collection()
+        .update_many(
+            doc! { "folder_id": folder_id },
+            doc! { "$set": { "folder_id": null } },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                anyhow::anyhow!(e),
+            )))
+        })?;
+
+    Ok(StatusCode::OK.into_response())
+}
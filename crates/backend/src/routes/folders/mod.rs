@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{delete as method_delete, get, post, put},
+};
+use model::folder::Folder;
+use mongodb::bson::oid::ObjectId;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    state::AppState,
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/folders", post(create::handler))
+        .route("/folders", get(list::handler))
+        .route("/folders/{folder_id}", put(update::handler))
+        .route("/folders/{folder_id}", method_delete(delete::handler))
+}
+
+pub async fn load_owned(
+    state: &AppState,
+    folder_id: ObjectId,
+    user_id: ObjectId,
+) -> Result<Folder, ApplicationError> {
+    let folder = state
+        .storage()
+        .database()
+        .folders
+        .get_by_id(folder_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?
+        .ok_or(ApplicationError::FolderDoesNotExist)?;
+
+    if folder.user_id != user_id {
+        return Err(ApplicationError::FolderDoesNotBelongToUser);
+    }
+
+    Ok(folder)
+}
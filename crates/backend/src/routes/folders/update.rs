@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::folder::Folder;
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+use validator::Validate;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::folders::FolderPayload,
+    routes::folders::{self, create::FolderBody},
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(folder_id): Path<ObjectId>,
+    Json(payload): Json<FolderBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let existing = folders::load_owned(&state, folder_id, session.user_id).await?;
+    let folder = Folder {
+        name: payload.name.trim().to_string(),
+        ..existing
+    };
+
+    state
+        .storage()
+        .database()
+        .folders
+        .update(folder_id, doc! { "$set": { "name": &folder.name } })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok((StatusCode::OK, Json(FolderPayload::from(folder))).into_response())
+}
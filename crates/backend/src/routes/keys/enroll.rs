@@ -17,7 +17,7 @@ use crate::{
     state::AppState,
 };
 
-const VALID_INFERENCE_PROVIDERS: &[&'static str] = &["openrouter"];
+const VALID_INFERENCE_PROVIDERS: &[&str] = &["openrouter"];
 
 #[derive(Debug, Deserialize)]
 pub struct KeyEnrollPayload {
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use model::memory::Memory;
+use mongodb::bson::oid::ObjectId;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    context::project,
+    data::tags,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    memories,
+    middleware::auth::Auth,
+    payload::memories::MemoryPayload,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MemoryBody {
+    #[validate(length(min = 1, max = 1000, message = "Content must be 1-1000 chars long."))]
+    pub content: String,
+    #[serde(default)]
+    #[validate(
+        length(max = 10, message = "A memory can have at most 10 tags."),
+        custom(function = "tags::validate")
+    )]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl MemoryBody {
+    pub fn tags(&self) -> Vec<String> {
+        tags::normalize(&self.tags)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMemoryBody {
+    #[serde(flatten)]
+    pub memory: MemoryBody,
+    /// Project the memory is scoped to. The project must have scoped memories enabled.
+    pub project_id: Option<ObjectId>,
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Json(payload): Json<CreateMemoryBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.memory.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let project = match payload.project_id {
+        Some(project_id) => Some(project::load_owned(&state, project_id, session.user_id).await?),
+        None => None,
+    };
+
+    let now = Utc::now();
+    let mut memory = Memory {
+        id: None,
+        user_id: session.user_id,
+        content: payload.memory.content.trim().to_string(),
+        project_id: project::memory_project_id(project.as_ref()),
+        indexed: false,
+        tags: payload.memory.tags(),
+        pinned: payload.memory.pinned,
+        source_message_id: None,
+        created_at: Some(now),
+        updated_at: Some(now),
+    };
+
+    let id = state
+        .storage()
+        .database()
+        .memories
+        .create(memory.clone())
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    memory.id = Some(id);
+    // left unindexed on failure, so the next backfill picks it up
+    if let Err(e) = memories::index(&state, &memory).await {
+        tracing::error!("Failed to index memory {id}: {e}");
+    } else {
+        memory.indexed = true;
+    }
+
+    Ok((StatusCode::OK, Json(MemoryPayload::from(memory))).into_response())
+}
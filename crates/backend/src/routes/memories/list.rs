@@ -1,9 +1,14 @@
 use std::sync::Arc;
 
-use axum::{Json, extract::State, response::IntoResponse};
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
 use futures::TryStreamExt;
-use mongodb::bson::doc;
+use mongodb::bson::{doc, oid::ObjectId};
 use reqwest::StatusCode;
+use serde::Deserialize;
 
 use crate::{
     errors::{
@@ -15,25 +20,43 @@ use crate::{
     state::AppState,
 };
 
+#[derive(Debug, Deserialize)]
+pub struct ListMemoriesPayload {
+    /// Only lists memories with this tag when set.
+    pub tag: Option<String>,
+    pub pinned: Option<bool>,
+    /// Only lists the memories scoped to this project when set.
+    pub project_id: Option<ObjectId>,
+}
+
 pub async fn handler(
     State(state): State<Arc<AppState>>,
     Auth(session): Auth,
+    Query(payload): Query<ListMemoriesPayload>,
 ) -> Result<impl IntoResponse, ApplicationError> {
+    let mut filter = doc! { "user_id": session.user_id };
+    if let Some(tag) = payload.tag {
+        filter.insert("tags", tag.trim().to_lowercase());
+    }
+    if let Some(pinned) = payload.pinned {
+        filter.insert("pinned", pinned);
+    }
+    if let Some(project_id) = payload.project_id {
+        filter.insert("project_id", project_id);
+    }
+
     let memories = state
         .storage()
         .database()
         .memories
-        .get_many(doc! { "user_id": session.user_id })
+        .get_many_sorted(filter, doc! { "pinned": -1, "_id": -1 })
         .await
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
 
     let memories = memories
-        .map_ok(|memory| MemoryPayload {
-            id: memory.id.unwrap(),
-            content: memory.content,
-        })
+        .map_ok(MemoryPayload::from)
         .try_collect::<Vec<MemoryPayload>>()
         .await
         .map_err(|e| {
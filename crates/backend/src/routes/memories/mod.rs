@@ -7,11 +7,16 @@ use axum::{
 
 use crate::state::AppState;
 
+pub mod create;
 pub mod list;
 pub mod remove;
+pub mod update;
 
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
-        .route("/memories", get(list::handler))
-        .route("/memories/{memory_id}", delete(remove::handler))
+        .route("/memories", get(list::handler).post(create::handler))
+        .route(
+            "/memories/{memory_id}",
+            delete(remove::handler).put(update::handler),
+        )
 }
@@ -12,6 +12,7 @@ use crate::{
         ApplicationError,
         storage::{StorageError, database::DatabaseError},
     },
+    memories,
     middleware::auth::Auth,
     state::AppState,
 };
@@ -48,6 +49,9 @@ pub async fn handler(
         .map_err(|e| {
             ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
         })?;
+    memories::remove(&state, memory_id).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+    })?;
 
     Ok((StatusCode::OK).into_response())
 }
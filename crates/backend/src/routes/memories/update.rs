@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use model::memory::Memory;
+use mongodb::bson::{self, doc, oid::ObjectId};
+use reqwest::StatusCode;
+use validator::Validate;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    memories,
+    middleware::auth::Auth,
+    payload::memories::MemoryPayload,
+    routes::memories::create::MemoryBody,
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(memory_id): Path<ObjectId>,
+    Json(payload): Json<MemoryBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let memory = state
+        .storage()
+        .database()
+        .memories
+        .get_by_id(memory_id)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    let Some(existing) = memory else {
+        return Err(ApplicationError::MemoryDoesNotExist);
+    };
+
+    if existing.user_id != session.user_id {
+        return Err(ApplicationError::MemoryDoesNotBelongToUser);
+    }
+
+    let content = payload.content.trim().to_string();
+    let content_changed = content != existing.content;
+    let now = Utc::now();
+    let mut memory = Memory {
+        content,
+        tags: payload.tags(),
+        pinned: payload.pinned,
+        created_at: existing
+            .created_at
+            .or(Some(memory_id.timestamp().to_chrono())),
+        updated_at: Some(now),
+        indexed: existing.indexed && !content_changed,
+        ..existing
+    };
+
+    state
+        .storage()
+        .database()
+        .memories
+        .update(
+            memory_id,
+            doc! {
+                "$set": {
+                    "content": &memory.content,
+                    "tags": &memory.tags,
+                    "pinned": memory.pinned,
+                    "created_at": memory.created_at.map(bson::DateTime::from_chrono),
+                    "updated_at": bson::DateTime::from_chrono(now),
+                    "indexed": memory.indexed,
+                }
+            },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    if content_changed {
+        // left unindexed on failure, so the next backfill picks it up
+        if let Err(e) = memories::index(&state, &memory).await {
+            tracing::error!("Failed to index memory {memory_id}: {e}");
+        } else {
+            memory.indexed = true;
+        }
+    }
+
+    Ok((StatusCode::OK, Json(MemoryPayload::from(memory))).into_response())
+}
@@ -1,9 +1,13 @@
+pub mod assistants;
 pub mod auth;
 pub mod chats;
 pub mod completion;
 pub mod files;
+pub mod folders;
 pub mod keys;
 pub mod memories;
+pub mod projects;
+pub mod search;
 pub mod service;
 pub mod users;
 
@@ -23,4 +27,8 @@ pub fn router() -> Router<Arc<AppState>> {
         .merge(keys::router())
         .merge(files::router())
         .merge(memories::router())
+        .merge(assistants::router())
+        .merge(projects::router())
+        .merge(folders::router())
+        .merge(search::router())
 }
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use model::project::Project;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::projects::ProjectPayload,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ProjectBody {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1-64 chars long."))]
+    pub name: String,
+    #[validate(length(
+        max = 20000,
+        message = "Instructions must be at most 20000 chars long."
+    ))]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub scoped_memories: bool,
+}
+
+impl ProjectBody {
+    pub fn instructions(&self) -> Option<String> {
+        self.instructions
+            .as_ref()
+            .map(|instructions| instructions.trim().to_string())
+            .filter(|instructions| !instructions.is_empty())
+    }
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Json(payload): Json<ProjectBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let mut project = Project {
+        id: None,
+        user_id: session.user_id,
+        name: payload.name.trim().to_string(),
+        instructions: payload.instructions(),
+        scoped_memories: payload.scoped_memories,
+        timestamp: Utc::now(),
+    };
+
+    let id = state
+        .storage()
+        .database()
+        .projects
+        .create(project.clone())
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    project.id = Some(id);
+
+    Ok((StatusCode::OK, Json(ProjectPayload::from(project))).into_response())
+}
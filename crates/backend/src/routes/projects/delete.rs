@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::vector::VectorNamespace;
+use mongodb::{
+    ClientSession,
+    bson::{Document, doc, oid::ObjectId},
+};
+use reqwest::StatusCode;
+
+use crate::{
+    context::project,
+    data::transaction,
+    documents,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    state::AppState,
+};
+
+/// Deletes the project with its scoped memories and the files only it uses. Chats outlive the
+/// project and become plain chats.
+///
+/// The rows are changed in one transaction when the server supports them. Otherwise the project
+/// is deleted last, so a request that fails halfway can be retried.
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(project_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    project::load_owned(&state, project_id, session.user_id).await?;
+
+    let database = state.storage().database();
+    // files that were also sent in a chat or pinned to an assistant are kept by them
+    let upload_ids = distinct_ids(
+        &database.uploads.collection(),
+        doc! { "project_id": project_id, "chat_id": null, "assistant_id": null },
+    )
+    .await?;
+    let memory_ids = distinct_ids(
+        &database.memories.collection(),
+        doc! { "project_id": project_id },
+    )
+    .await?;
+
+    // embeddings are not covered by the transaction, so they go first
+    for upload_id in &upload_ids {
+        state
+            .storage()
+            .vectors()
+            .delete_group(VectorNamespace::Documents, *upload_id)
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+    }
+    state
+        .storage()
+        .vectors()
+        .delete(VectorNamespace::Memories, &memory_ids)
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    let result = match delete_in_transaction(&state, project_id, &upload_ids).await {
+        Err(e) if transaction::unsupported(&e) => {
+            delete_rows(&state, project_id, &upload_ids, None).await
+        }
+        result => result,
+    };
+    result.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            e.into(),
+        )))
+    })?;
+
+    Ok(StatusCode::OK.into_response())
+}
+
+async fn distinct_ids<T: Send + Sync>(
+    collection: &mongodb::Collection<T>,
+    filter: Document,
+) -> Result<Vec<ObjectId>, ApplicationError> {
+    let ids = collection.distinct("_id", filter).await.map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            e.into(),
+        )))
+    })?;
+
+    Ok(ids.into_iter().filter_map(|id| id.as_object_id()).collect())
+}
+
+async fn delete_in_transaction(
+    state: &AppState,
+    project_id: ObjectId,
+    upload_ids: &[ObjectId],
+) -> mongodb::error::Result<()> {
+    let mut session = state.storage().database().client().start_session().await?;
+    session.start_transaction().await?;
+
+    match delete_rows(state, project_id, upload_ids, Some(&mut session)).await {
+        Ok(()) => session.commit_transaction().await,
+        Err(e) => {
+            let _ = session.abort_transaction().await;
+            Err(e)
+        }
+    }
+}
+
+/// Detaches the project's chats and shared files and deletes the rest, the project last.
+async fn delete_rows(
+    state: &AppState,
+    project_id: ObjectId,
+    upload_ids: &[ObjectId],
+    mut session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<()> {
+    let database = state.storage().database();
+
+    documents::delete_rows(state, upload_ids, session.as_deref_mut()).await?;
+    transaction::update_many(
+        &database.uploads.collection(),
+        doc! { "project_id": project_id },
+        doc! { "$set": { "project_id": null } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    transaction::update_many(
+        &database.chats.collection(),
+        doc! { "project_id": project_id },
+        doc! { "$set": { "project_id": null } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    // scoped memories are meaningless without the project
+    transaction::delete_many(
+        &database.memories.collection(),
+        doc! { "project_id": project_id },
+        session.as_deref_mut(),
+    )
+    .await?;
+    transaction::delete_many(
+        &database.projects.collection(),
+        doc! { "_id": project_id },
+        session,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+
+use crate::{
+    context::project, errors::ApplicationError, middleware::auth::Auth,
+    payload::upload::UserUploadPayload, state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(project_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    project::load_owned(&state, project_id, session.user_id).await?;
+
+    let uploads = project::files(&state, project_id)
+        .await?
+        .into_iter()
+        .map(|upload| UserUploadPayload {
+            id: upload.id,
+            chat_id: upload.chat_id,
+            user_id: upload.user_id,
+            content_type: upload.content_type,
+            ingestion: upload.ingestion,
+        })
+        .collect::<Vec<_>>();
+
+    Ok((StatusCode::OK, Json(uploads)).into_response())
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{delete as method_delete, get, post, put},
+};
+
+use crate::state::AppState;
+
+pub mod add_file;
+pub mod create;
+pub mod delete;
+pub mod files;
+pub mod list;
+pub mod remove_file;
+pub mod state;
+pub mod update;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/projects", post(create::handler))
+        .route("/projects", get(list::handler))
+        .route("/projects/{project_id}", get(state::handler))
+        .route("/projects/{project_id}", put(update::handler))
+        .route("/projects/{project_id}", method_delete(delete::handler))
+        .route("/projects/{project_id}/files", get(files::handler))
+        .route(
+            "/projects/{project_id}/files/{upload_id}",
+            post(add_file::handler),
+        )
+        .route(
+            "/projects/{project_id}/files/{upload_id}",
+            method_delete(remove_file::handler),
+        )
+}
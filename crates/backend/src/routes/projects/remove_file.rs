@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+
+use crate::{
+    context::project,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path((project_id, upload_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    project::load_owned(&state, project_id, session.user_id).await?;
+
+    let upload = state
+        .storage()
+        .database()
+        .uploads
+        .get(doc! { "_id": upload_id, "project_id": project_id })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+    if upload.is_none() {
+        return Err(ApplicationError::UploadNotFound);
+    }
+
+    state
+        .storage()
+        .database()
+        .uploads
+        .update(upload_id, doc! { "$set": { "project_id": null } })
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok(StatusCode::OK.into_response())
+}
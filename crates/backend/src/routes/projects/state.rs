@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use mongodb::bson::oid::ObjectId;
+use reqwest::StatusCode;
+
+use crate::{
+    context::project, errors::ApplicationError, middleware::auth::Auth,
+    payload::projects::ProjectPayload, state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(project_id): Path<ObjectId>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let project = project::load_owned(&state, project_id, session.user_id).await?;
+
+    Ok((StatusCode::OK, Json(ProjectPayload::from(project))).into_response())
+}
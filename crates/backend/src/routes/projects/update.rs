@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use model::project::Project;
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+use validator::Validate;
+
+use crate::{
+    context::project,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::projects::ProjectPayload,
+    routes::projects::create::ProjectBody,
+    state::AppState,
+};
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Path(project_id): Path<ObjectId>,
+    Json(payload): Json<ProjectBody>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let existing = project::load_owned(&state, project_id, session.user_id).await?;
+    let project = Project {
+        name: payload.name.trim().to_string(),
+        instructions: payload.instructions(),
+        scoped_memories: payload.scoped_memories,
+        ..existing
+    };
+
+    state
+        .storage()
+        .database()
+        .projects
+        .update(
+            project_id,
+            doc! {
+                "$set": {
+                    "name": &project.name,
+                    "instructions": &project.instructions,
+                    "scoped_memories": project.scoped_memories,
+                }
+            },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok((StatusCode::OK, Json(ProjectPayload::from(project))).into_response())
+}
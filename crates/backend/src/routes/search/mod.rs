@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+pub mod semantic;
+pub mod text;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/search", get(text::handler))
+        .route("/search/semantic", get(semantic::handler))
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    history::{self, SearchFilters, snippet},
+    middleware::auth::Auth,
+    payload::{
+        chat::ChatPayload,
+        search::{ChatSearchResultPayload, MessageSnippetPayload, SearchResultsPayload},
+    },
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SemanticSearchPayload {
+    /// Description of the conversations to find.
+    #[validate(length(min = 1, max = 500, message = "Query must be 1-500 chars long."))]
+    pub q: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Only matches messages written by this model.
+    pub model: Option<String>,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 50, message = "Limit must be 1-50."))]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Query(payload): Query<SemanticSearchPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    let filters = SearchFilters {
+        from: payload.from,
+        to: payload.to,
+        model: payload.model,
+    };
+    let matches =
+        history::semantic::search(&state, session.user_id, &payload.q, &filters, payload.limit)
+            .await
+            .map_err(|e| {
+                ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+                    e,
+                )))
+            })?;
+
+    // words of the query that occur verbatim are still highlighted
+    let terms = snippet::terms(&payload.q);
+    let results = matches
+        .into_iter()
+        .map(|found| ChatSearchResultPayload {
+            name_highlights: vec![],
+            snippets: found
+                .messages
+                .into_iter()
+                .map(|(message, score)| {
+                    let snippet = snippet::snippet(&history::message_text(&message), &terms);
+                    MessageSnippetPayload {
+                        message_id: message.id.unwrap(),
+                        role: message.role,
+                        model: message.model,
+                        timestamp: message.timestamp,
+                        text: snippet.text,
+                        highlights: snippet.highlights.into_iter().map(Into::into).collect(),
+                        score: Some(score),
+                    }
+                })
+                .collect(),
+            chat: ChatPayload::from(found.chat),
+            score: Some(found.score),
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(SearchResultsPayload {
+            results,
+            next_cursor: None,
+        }),
+    )
+        .into_response())
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    data::cursor::Cursor,
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    history::{self, SearchFilters, snippet},
+    middleware::auth::Auth,
+    payload::{
+        chat::ChatPayload,
+        search::{ChatSearchResultPayload, MessageSnippetPayload, SearchResultsPayload},
+    },
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct TextSearchPayload {
+    /// MongoDB text search query: words, "quoted phrases" and -negated words.
+    #[validate(length(min = 1, max = 200, message = "Query must be 1-200 chars long."))]
+    pub q: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Only matches messages written by this model.
+    pub model: Option<String>,
+    pub cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 50, message = "Limit must be 1-50."))]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Query(payload): Query<TextSearchPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+    let cursor = match payload.cursor {
+        Some(ref cursor) => Some(Cursor::decode(cursor).ok_or(ApplicationError::InvalidCursor)?),
+        None => None,
+    };
+
+    let filters = SearchFilters {
+        from: payload.from,
+        to: payload.to,
+        model: payload.model,
+    };
+    let (matches, next_cursor) = history::text_search(
+        &state,
+        session.user_id,
+        &payload.q,
+        &filters,
+        cursor,
+        payload.limit,
+    )
+    .await
+    .map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+    })?;
+
+    let terms = snippet::terms(&payload.q);
+    let results = matches
+        .into_iter()
+        .map(|found| {
+            let name_highlights = found
+                .chat
+                .name
+                .as_deref()
+                .map(|name| snippet::highlights(name, &terms))
+                .unwrap_or_default();
+
+            ChatSearchResultPayload {
+                snippets: found
+                    .messages
+                    .into_iter()
+                    .map(|message| {
+                        let snippet = snippet::snippet(&history::message_text(&message), &terms);
+                        MessageSnippetPayload {
+                            message_id: message.id.unwrap(),
+                            role: message.role,
+                            model: message.model,
+                            timestamp: message.timestamp,
+                            text: snippet.text,
+                            highlights: snippet.highlights.into_iter().map(Into::into).collect(),
+                            score: None,
+                        }
+                    })
+                    .collect(),
+                name_highlights: name_highlights.into_iter().map(Into::into).collect(),
+                chat: ChatPayload::from(found.chat),
+                score: None,
+            }
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(SearchResultsPayload {
+            results,
+            next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        }),
+    )
+        .into_response())
+}
@@ -6,11 +6,20 @@ use serde_json::json;
 use crate::state::AppState;
 
 pub async fn handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let free_models = state.models().free_models();
-    let paid_models = state.models().paid_models();
+    let models = state.models();
+    let free_models: Vec<_> = models
+        .free_models()
+        .iter()
+        .filter(|model| model.available)
+        .collect();
+    let paid_models: Vec<_> = models
+        .paid_models()
+        .iter()
+        .filter(|model| model.available)
+        .collect();
 
     (
         StatusCode::OK,
-        Json(json!({ "free": free_models.to_vec(), "paid": paid_models.to_vec() })),
+        Json(json!({ "free": free_models, "paid": paid_models })),
     )
 }
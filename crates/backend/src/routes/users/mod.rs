@@ -1,11 +1,19 @@
 use std::sync::Arc;
 
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, put},
+};
 
 use crate::state::AppState;
 
 pub mod me;
+pub mod settings;
+pub mod update_settings;
 
 pub fn router() -> Router<Arc<AppState>> {
-    Router::new().route("/users/me", get(me::handler))
+    Router::new()
+        .route("/users/me", get(me::handler))
+        .route("/users/me/settings", get(settings::handler))
+        .route("/users/me/settings", put(update_settings::handler))
 }
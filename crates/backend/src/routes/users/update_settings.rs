@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono_tz::Tz;
+use model::user::UserSettings;
+use mongodb::bson::{self, doc};
+use serde::Deserialize;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    errors::{
+        ApplicationError,
+        storage::{StorageError, database::DatabaseError},
+    },
+    middleware::auth::Auth,
+    payload::users::UserSettingsPayload,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateUserSettingsPayload {
+    #[validate(length(max = 3000, message = "About me must be at most 3000 chars long."))]
+    pub about_me: Option<String>,
+    #[validate(length(
+        max = 3000,
+        message = "Response instructions must be at most 3000 chars long."
+    ))]
+    pub response_instructions: Option<String>,
+    pub default_model: Option<String>,
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+    #[validate(range(min = 1, max = 36500, message = "Retention must be 1-36500 days."))]
+    pub retention_days: Option<u32>,
+}
+
+fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    timezone
+        .parse::<Tz>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("timezone").with_message("Invalid time zone.".into()))
+}
+
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    let is_valid = !locale.is_empty()
+        && locale.len() <= 35
+        && locale
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("locale").with_message("Invalid locale.".into()))
+    }
+}
+
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Auth(session): Auth,
+    Json(payload): Json<UpdateUserSettingsPayload>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    if let Err(errors) = payload.validate() {
+        return Err(ApplicationError::ValidationError(errors));
+    }
+
+    if payload
+        .default_model
+        .as_ref()
+        .is_some_and(|identifier| state.models().find(identifier).is_none())
+    {
+        return Err(ApplicationError::InvalidModelIdentifier);
+    }
+
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let settings = UserSettings {
+        about_me: non_empty(payload.about_me),
+        response_instructions: non_empty(payload.response_instructions),
+        default_model: payload.default_model,
+        timezone: payload.timezone,
+        locale: payload.locale,
+        retention_days: payload.retention_days,
+    };
+    let settings_bson = bson::to_bson(&settings).map_err(|e| {
+        ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(
+            anyhow::anyhow!(e),
+        )))
+    })?;
+
+    state
+        .storage()
+        .database()
+        .users
+        .update(
+            session.user_id,
+            doc! { "$set": { "settings": settings_bson } },
+        )
+        .await
+        .map_err(|e| {
+            ApplicationError::StorageError(StorageError::DatabaseError(DatabaseError::Unknown(e)))
+        })?;
+
+    Ok((StatusCode::OK, Json(UserSettingsPayload::from(settings))).into_response())
+}
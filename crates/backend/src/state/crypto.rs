@@ -57,7 +57,7 @@ impl CryptoState {
         use aes_gcm::KeyInit;
 
         let key = Key::<Aes256Gcm>::from_slice(&self.key_encryption_secret);
-        let cipher = Aes256Gcm::new(&key);
+        let cipher = Aes256Gcm::new(key);
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
         let ciphertext = cipher.encrypt(&nonce, plaintext).unwrap();
         let result = format!("{}.{}", hex::encode(nonce), hex::encode(ciphertext));
@@ -73,7 +73,7 @@ impl CryptoState {
         let ciphertext = hex::decode(ciphertext).unwrap();
 
         let key = Key::<Aes256Gcm>::from_slice(&self.key_encryption_secret);
-        let cipher = Aes256Gcm::new(&key);
+        let cipher = Aes256Gcm::new(key);
         let plaintext = cipher
             .decrypt(GenericArray::from_slice(&nonce), ciphertext.as_ref())
             .unwrap();
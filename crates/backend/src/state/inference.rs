@@ -2,11 +2,73 @@ use std::env;
 
 use ai::openai::client::OpenAIClient;
 use anyhow::{Context, anyhow};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_EMBEDDING_MODEL: &str = "openai/text-embedding-3-small";
+/// Auxiliary model used when Chutes is the auxiliary provider and no model is configured.
+pub const DEFAULT_AUXILIARY_MODEL: &str = "zai-org/GLM-4.5-Air";
+/// Inputs embedded per request to the embeddings API.
+const EMBEDDING_BATCH: usize = 64;
 
 pub struct InferenceState {
     pub openrouter: OpenAIClient,
-    pub chutes: OpenAIClient,
+    /// Unset when no Chutes API key is configured.
+    pub chutes: Option<OpenAIClient>,
+    /// OpenRouter model used to embed documents, memories and messages.
+    pub embedding_model: String,
+    /// Requested vector size. Must match the vector index when MongoDB Atlas search is used.
+    pub embedding_dimensions: Option<u32>,
+    pub auxiliary: AuxiliaryConfig,
+}
+
+/// Model for background tasks such as chat names, memories and summaries.
+#[derive(Debug, Clone)]
+pub struct AuxiliaryConfig {
+    pub provider: InferenceProvider,
+    /// Unset to run the tasks with the chat's own model.
+    pub model: Option<String>,
+    pub titles: bool,
+    pub memories: bool,
+    pub summaries: bool,
+    /// Every this many user turns, the chat is renamed if its topic drifted.
+    pub retitle_turns: Option<usize>,
+}
+
+impl AuxiliaryConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        let provider = match env::var("AUXILIARY_PROVIDER") {
+            Ok(provider) => InferenceProvider::try_from(provider.as_str())?,
+            Err(_) => InferenceProvider::Chutes,
+        };
+        let model = env::var("AUXILIARY_MODEL").ok().or_else(|| {
+            matches!(provider, InferenceProvider::Chutes)
+                .then(|| DEFAULT_AUXILIARY_MODEL.to_string())
+        });
+
+        Ok(Self {
+            provider,
+            model,
+            titles: flag("AUXILIARY_TITLES")?,
+            memories: flag("AUXILIARY_MEMORIES")?,
+            summaries: flag("AUXILIARY_SUMMARIES")?,
+            retitle_turns: env::var("AUXILIARY_RETITLE_TURNS")
+                .ok()
+                .map(|turns| turns.parse())
+                .transpose()
+                .context("Invalid retitle interval")?
+                .filter(|turns| *turns > 0),
+        })
+    }
+}
+
+/// Boolean environment variable that defaults to enabled.
+fn flag(name: &str) -> anyhow::Result<bool> {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .with_context(|| format!("{name} must be `true` or `false`")),
+        Err(_) => Ok(true),
+    }
 }
 
 impl InferenceState {
@@ -16,15 +78,49 @@ impl InferenceState {
                 env::var("OPENROUTER_KEY").context("Missing OpenRouter API key")?,
                 "https://openrouter.ai/api".to_string(),
             ),
-            chutes: OpenAIClient::new(
-                env::var("CHUTES_KEY").context("Missing Chutes API key")?,
-                "https://llm.chutes.ai".to_string(),
-            ),
+            chutes: env::var("CHUTES_KEY")
+                .ok()
+                .map(|key| OpenAIClient::new(key, "https://llm.chutes.ai".to_string())),
+            embedding_model: env::var("EMBEDDING_MODEL")
+                .unwrap_or_else(|_| DEFAULT_EMBEDDING_MODEL.to_string()),
+            embedding_dimensions: env::var("EMBEDDING_DIMENSIONS")
+                .ok()
+                .map(|dimensions| dimensions.parse())
+                .transpose()
+                .context("Invalid embedding dimensions")?,
+            auxiliary: AuxiliaryConfig::from_env()?,
         })
     }
+
+    /// Client for a provider, if it is configured.
+    pub fn client(&self, provider: InferenceProvider) -> Option<OpenAIClient> {
+        match provider {
+            InferenceProvider::OpenRouter => Some(self.openrouter.clone()),
+            InferenceProvider::Chutes => self.chutes.clone(),
+        }
+    }
+
+    /// Embeds `input` with the configured model, batching large inputs.
+    pub async fn embed(&self, input: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(input.len());
+        for batch in input.chunks(EMBEDDING_BATCH) {
+            embeddings.extend(
+                self.openrouter
+                    .clone()
+                    .embeddings(
+                        self.embedding_model.clone(),
+                        batch.to_vec(),
+                        self.embedding_dimensions,
+                    )
+                    .await?,
+            );
+        }
+
+        Ok(embeddings)
+    }
 }
 
-#[derive(Debug, Serialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 pub enum InferenceProvider {
     OpenRouter,
     Chutes,
@@ -1,15 +1,20 @@
 use std::{
     collections::HashMap,
     env,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
 };
 
 use crate::{
+    jobs::queue::JobQueue,
     models::ModelsConfig,
     state::{crypto::CryptoState, inference::InferenceState, storage::StorageState},
-    streaming::ApiDelta,
+    streaming::{ApiDelta, ControlChunk},
+    temporary,
 };
+use ai::openai::models::OpenAIModel;
 use anyhow::Context;
+use mongodb::bson::oid::ObjectId;
 use search::{SearchClient, serper::SerperSearchClient};
 use uuid::Uuid;
 
@@ -17,26 +22,49 @@ pub mod crypto;
 pub mod inference;
 pub mod storage;
 
+/// Catalog from the config file and the latest model list fetched from OpenRouter.
+struct CatalogSources {
+    config: Arc<ModelsConfig>,
+    upstream: Option<Vec<OpenAIModel>>,
+}
+
 pub struct AppState {
     inference: InferenceState,
     streams: Arc<Mutex<HashMap<Uuid, flume::Receiver<ApiDelta>>>>,
+    /// Response streams of each chat, held weakly so finished streams can close.
+    chat_streams: Mutex<HashMap<ObjectId, Vec<flume::WeakSender<ApiDelta>>>>,
     storage: StorageState,
     crypto: CryptoState,
-    models: ModelsConfig,
+    models: RwLock<Arc<ModelsConfig>>,
+    /// Inputs the served catalog is built from. Held while a new catalog is built, so a reload
+    /// and a sync cannot overwrite each other's result.
+    catalog: Mutex<CatalogSources>,
     search: Arc<dyn SearchClient>,
+    jobs: JobQueue,
+    temporary_chat_ttl: Duration,
 }
 
 impl AppState {
     pub async fn new() -> anyhow::Result<Self> {
+        let storage = StorageState::new().await?;
+        let models = Arc::new(ModelsConfig::load(ModelsConfig::config_path())?);
+
         Ok(Self {
             inference: InferenceState::new()?,
             streams: Default::default(),
-            storage: StorageState::new().await?,
+            chat_streams: Default::default(),
+            jobs: JobQueue::new(storage.cache().connection()),
+            storage,
             crypto: CryptoState::new()?,
-            models: ModelsConfig::new(),
+            models: RwLock::new(Arc::clone(&models)),
+            catalog: Mutex::new(CatalogSources {
+                config: models,
+                upstream: None,
+            }),
             search: Arc::new(SerperSearchClient::new(
                 env::var("SERPER_KEY").context("Missing OpenRouter API key")?,
             )),
+            temporary_chat_ttl: temporary::ttl()?,
         })
     }
 
@@ -52,8 +80,61 @@ impl AppState {
         self.streams.lock().unwrap().remove(id).is_some()
     }
 
-    pub fn models(&self) -> &ModelsConfig {
-        &self.models
+    /// Registers a response stream of the chat for [`Self::notify_chat`].
+    pub fn watch_chat(&self, chat_id: ObjectId, stream: &flume::Sender<ApiDelta>) {
+        let mut chat_streams = self.chat_streams.lock().unwrap();
+        chat_streams.retain(|_, streams| {
+            streams.retain(|stream| stream.upgrade().is_some());
+            !streams.is_empty()
+        });
+        chat_streams
+            .entry(chat_id)
+            .or_default()
+            .push(stream.downgrade());
+    }
+
+    /// Sends a control chunk to every open response stream of the chat on this instance.
+    pub fn notify_chat(&self, chat_id: ObjectId, chunk: ControlChunk) {
+        let streams: Vec<_> = self
+            .chat_streams
+            .lock()
+            .unwrap()
+            .get(&chat_id)
+            .map(|streams| {
+                streams
+                    .iter()
+                    .filter_map(|stream| stream.upgrade())
+                    .collect()
+            })
+            .unwrap_or_default();
+        for stream in streams {
+            let _ = stream.send(ApiDelta::Control(chunk.clone()));
+        }
+    }
+
+    pub fn models(&self) -> Arc<ModelsConfig> {
+        Arc::clone(&self.models.read().unwrap())
+    }
+
+    pub fn set_models(&self, models: ModelsConfig) {
+        let mut catalog = self.catalog.lock().unwrap();
+        catalog.config = Arc::new(models);
+        self.publish_models(&catalog);
+    }
+
+    pub fn set_upstream_models(&self, upstream: Vec<OpenAIModel>) {
+        let mut catalog = self.catalog.lock().unwrap();
+        catalog.upstream = Some(upstream);
+        self.publish_models(&catalog);
+    }
+
+    /// Rebuilds the served catalog from the config file and the upstream list.
+    fn publish_models(&self, catalog: &CatalogSources) {
+        let models = match catalog.upstream {
+            Some(ref upstream) => Arc::new(catalog.config.merged(upstream)),
+            None => Arc::clone(&catalog.config),
+        };
+        *self.models.write().unwrap() = models;
     }
 
     pub fn inference(&self) -> &InferenceState {
@@ -71,4 +152,13 @@ impl AppState {
     pub fn crypto(&self) -> &CryptoState {
         &self.crypto
     }
+
+    pub fn jobs(&self) -> &JobQueue {
+        &self.jobs
+    }
+
+    /// How long a temporary chat is kept after its last message.
+    pub fn temporary_chat_ttl(&self) -> Duration {
+        self.temporary_chat_ttl
+    }
 }
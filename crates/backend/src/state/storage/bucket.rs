@@ -6,6 +6,10 @@ use mongodb::{
     options::{GridFsBucketOptions, WriteConcern},
 };
 
+/// GridFS bucket holding uploaded files, stored in the `attachments.files` and
+/// `attachments.chunks` collections.
+pub const BUCKET_NAME: &str = "attachments";
+
 pub struct BucketState {
     bucket: GridFsBucket,
 }
@@ -13,7 +17,7 @@ pub struct BucketState {
 impl BucketState {
     pub async fn new(client: Client) -> anyhow::Result<Self> {
         let gridfs_opts = GridFsBucketOptions::builder()
-            .bucket_name("attachments".to_string())
+            .bucket_name(BUCKET_NAME.to_string())
             .write_concern(
                 WriteConcern::builder()
                     .w_timeout(Duration::new(5, 0))
@@ -1,17 +1,25 @@
 use model::{
-    chat::Chat, key::ApiKey, memory::Memory, message::ChatMessage, upload::UserUpload, user::User,
+    assistant::Assistant, chat::Chat, document::DocumentChunk, folder::Folder, key::ApiKey,
+    memory::Memory, message::ChatMessage, project::Project, retention::RetentionAudit,
+    upload::UserUpload, user::User,
 };
 use mongodb::{Client, IndexModel, bson::doc, options::IndexOptions};
 
 use crate::data::mongodb::MongoDataAdapter;
 
 pub struct DatabaseState {
+    client: Client,
     pub users: MongoDataAdapter<User>,
     pub chats: MongoDataAdapter<Chat>,
     pub messages: MongoDataAdapter<ChatMessage>,
     pub keys: MongoDataAdapter<ApiKey>,
     pub uploads: MongoDataAdapter<UserUpload>,
     pub memories: MongoDataAdapter<Memory>,
+    pub assistants: MongoDataAdapter<Assistant>,
+    pub projects: MongoDataAdapter<Project>,
+    pub folders: MongoDataAdapter<Folder>,
+    pub chunks: MongoDataAdapter<DocumentChunk>,
+    pub retention_audits: MongoDataAdapter<RetentionAudit>,
 }
 
 impl DatabaseState {
@@ -19,6 +27,7 @@ impl DatabaseState {
         Self::migrate(&client).await?;
 
         Ok(Self {
+            client: client.clone(),
             users: MongoDataAdapter::new(client.clone(), "chat".to_string(), "users".to_string()),
             chats: MongoDataAdapter::new(client.clone(), "chat".to_string(), "chats".to_string()),
             messages: MongoDataAdapter::new(
@@ -32,10 +41,39 @@ impl DatabaseState {
                 "chat".to_string(),
                 "uploads".to_string(),
             ),
-            memories: MongoDataAdapter::new(client, "chat".to_string(), "memories".to_string()),
+            memories: MongoDataAdapter::new(
+                client.clone(),
+                "chat".to_string(),
+                "memories".to_string(),
+            ),
+            assistants: MongoDataAdapter::new(
+                client.clone(),
+                "chat".to_string(),
+                "assistants".to_string(),
+            ),
+            projects: MongoDataAdapter::new(
+                client.clone(),
+                "chat".to_string(),
+                "projects".to_string(),
+            ),
+            folders: MongoDataAdapter::new(
+                client.clone(),
+                "chat".to_string(),
+                "folders".to_string(),
+            ),
+            chunks: MongoDataAdapter::new(client.clone(), "chat".to_string(), "chunks".to_string()),
+            retention_audits: MongoDataAdapter::new(
+                client,
+                "chat".to_string(),
+                "retention_audits".to_string(),
+            ),
         })
     }
 
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
     async fn migrate(client: &Client) -> anyhow::Result<()> {
         client.database("chat").create_collection("users").await?;
         client
@@ -54,6 +92,29 @@ impl DatabaseState {
             .collection::<ChatMessage>("chats")
             .create_index(IndexModel::builder().keys(doc! { "user_id": 1 }).build())
             .await?;
+        client
+            .database("chat")
+            .collection::<Chat>("chats")
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "user_id": 1, "updated_at": -1, "_id": -1 })
+                    .build(),
+            )
+            .await?;
+        // chats created before activity was tracked are listed by their creation time
+        client
+            .database("chat")
+            .collection::<Chat>("chats")
+            .update_many(
+                doc! { "updated_at": { "$exists": false } },
+                vec![doc! { "$set": { "updated_at": "$timestamp" } }],
+            )
+            .await?;
+        client
+            .database("chat")
+            .collection::<Chat>("chats")
+            .create_index(IndexModel::builder().keys(doc! { "name": "text" }).build())
+            .await?;
         client
             .database("chat")
             .create_collection("messages")
@@ -63,6 +124,24 @@ impl DatabaseState {
             .collection::<ChatMessage>("messages")
             .create_index(IndexModel::builder().keys(doc! { "chat_id": 1 }).build())
             .await?;
+        client
+            .database("chat")
+            .collection::<ChatMessage>("messages")
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "chat_id": 1, "timestamp": -1, "_id": -1 })
+                    .build(),
+            )
+            .await?;
+        client
+            .database("chat")
+            .collection::<ChatMessage>("messages")
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "content.value": "text" })
+                    .build(),
+            )
+            .await?;
 
         client
             .database("chat")
@@ -79,6 +158,31 @@ impl DatabaseState {
             .collection::<Memory>("memories")
             .create_index(IndexModel::builder().keys(doc! { "user_id": 1 }).build())
             .await?;
+        client
+            .database("chat")
+            .collection::<Assistant>("assistants")
+            .create_index(IndexModel::builder().keys(doc! { "user_id": 1 }).build())
+            .await?;
+        client
+            .database("chat")
+            .collection::<Project>("projects")
+            .create_index(IndexModel::builder().keys(doc! { "user_id": 1 }).build())
+            .await?;
+        client
+            .database("chat")
+            .collection::<Folder>("folders")
+            .create_index(IndexModel::builder().keys(doc! { "user_id": 1 }).build())
+            .await?;
+        client
+            .database("chat")
+            .collection::<DocumentChunk>("chunks")
+            .create_index(IndexModel::builder().keys(doc! { "upload_id": 1 }).build())
+            .await?;
+        client
+            .database("chat")
+            .collection::<RetentionAudit>("retention_audits")
+            .create_index(IndexModel::builder().keys(doc! { "user_id": 1 }).build())
+            .await?;
 
         Ok(())
     }
@@ -1,19 +1,21 @@
 pub mod bucket;
 pub mod cache;
 pub mod database;
+pub mod vectors;
 
-use std::env;
+use std::{env, sync::Arc};
 
 use anyhow::Context;
 use database::DatabaseState;
 use mongodb::{Client, options::ClientOptions};
 
-use crate::state::storage::{bucket::BucketState, cache::CacheState};
+use crate::state::storage::{bucket::BucketState, cache::CacheState, vectors::VectorStore};
 
 pub struct StorageState {
     database: DatabaseState,
     cache: CacheState,
     bucket: BucketState,
+    vectors: Arc<dyn VectorStore>,
 }
 
 impl StorageState {
@@ -25,6 +27,7 @@ impl StorageState {
         Ok(Self {
             database: DatabaseState::new(client.clone()).await?,
             cache: CacheState::new().await?,
+            vectors: vectors::from_env(client.clone()).await?,
             bucket: BucketState::new(client).await?,
         })
     }
@@ -40,4 +43,8 @@ impl StorageState {
     pub fn bucket(&self) -> &BucketState {
         &self.bucket
     }
+
+    pub fn vectors(&self) -> &dyn VectorStore {
+        self.vectors.as_ref()
+    }
 }
@@ -0,0 +1,77 @@
+use futures::TryStreamExt;
+use model::vector::{VectorNamespace, VectorRecord};
+use mongodb::bson::{doc, oid::ObjectId};
+
+use crate::{
+    data::mongodb::MongoDataAdapter,
+    state::storage::vectors::{self, VectorMatch, VectorQuery, VectorStore},
+};
+
+/// Candidates considered per requested result. Higher improves recall at the cost of latency.
+const CANDIDATES_PER_RESULT: usize = 20;
+
+/// Queries a MongoDB Atlas vector search index over the `vectors` collection.
+///
+/// The index must use cosine similarity on `vector` and declare `namespace`, `user_id` and
+/// `group` as filter fields.
+pub struct AtlasVectorStore {
+    records: MongoDataAdapter<VectorRecord>,
+    index: String,
+}
+
+impl AtlasVectorStore {
+    pub fn new(records: MongoDataAdapter<VectorRecord>, index: String) -> Self {
+        Self { records, index }
+    }
+}
+
+#[async_trait::async_trait]
+impl VectorStore for AtlasVectorStore {
+    async fn upsert(&self, records: Vec<VectorRecord>) -> anyhow::Result<()> {
+        vectors::upsert(&self.records, records).await
+    }
+
+    async fn query(&self, query: VectorQuery) -> anyhow::Result<Vec<VectorMatch>> {
+        let pipeline = vec![
+            doc! {
+                "$vectorSearch": {
+                    "index": &self.index,
+                    "path": "vector",
+                    "queryVector": &query.vector,
+                    "numCandidates": (query.limit * CANDIDATES_PER_RESULT) as i64,
+                    "limit": query.limit as i64,
+                    "filter": vectors::filter(&query),
+                }
+            },
+            doc! { "$project": { "_id": 1, "score": { "$meta": "vectorSearchScore" } } },
+        ];
+
+        let mut cursor = self.records.collection().aggregate(pipeline).await?;
+        let mut matches = vec![];
+        while let Some(document) = cursor.try_next().await? {
+            // Atlas normalizes cosine similarity to [0, 1]
+            let score = 2. * document.get_f64("score")? as f32 - 1.;
+            if score < query.min_score {
+                continue;
+            }
+            matches.push(VectorMatch {
+                id: document.get_object_id("_id")?,
+                score,
+            });
+        }
+
+        Ok(matches)
+    }
+
+    async fn delete(&self, namespace: VectorNamespace, ids: &[ObjectId]) -> anyhow::Result<()> {
+        vectors::delete(&self.records, namespace, ids).await
+    }
+
+    async fn delete_group(
+        &self,
+        namespace: VectorNamespace,
+        group: ObjectId,
+    ) -> anyhow::Result<()> {
+        vectors::delete_group(&self.records, namespace, group).await
+    }
+}
@@ -0,0 +1,64 @@
+use futures::TryStreamExt;
+use model::vector::{VectorNamespace, VectorRecord};
+use mongodb::bson::oid::ObjectId;
+
+use crate::{
+    data::mongodb::MongoDataAdapter,
+    state::storage::vectors::{self, VectorMatch, VectorQuery, VectorStore},
+};
+
+/// Scores every record matching the query's filter in process.
+///
+/// Queries are always narrowed to a user or a set of groups, so the candidate sets stay in the
+/// thousands, where an exact scan is fast enough and needs no index to keep in sync.
+pub struct BruteForceVectorStore {
+    records: MongoDataAdapter<VectorRecord>,
+}
+
+impl BruteForceVectorStore {
+    pub fn new(records: MongoDataAdapter<VectorRecord>) -> Self {
+        Self { records }
+    }
+}
+
+#[async_trait::async_trait]
+impl VectorStore for BruteForceVectorStore {
+    async fn upsert(&self, records: Vec<VectorRecord>) -> anyhow::Result<()> {
+        vectors::upsert(&self.records, records).await
+    }
+
+    async fn query(&self, query: VectorQuery) -> anyhow::Result<Vec<VectorMatch>> {
+        let mut matches = self
+            .records
+            .get_many(vectors::filter(&query))
+            .await?
+            .try_filter_map(|record| {
+                let score = vectors::cosine(&query.vector, &record.vector);
+                async move {
+                    Ok((score >= query.min_score).then_some(VectorMatch {
+                        id: record.id,
+                        score,
+                    }))
+                }
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(query.limit);
+
+        Ok(matches)
+    }
+
+    async fn delete(&self, namespace: VectorNamespace, ids: &[ObjectId]) -> anyhow::Result<()> {
+        vectors::delete(&self.records, namespace, ids).await
+    }
+
+    async fn delete_group(
+        &self,
+        namespace: VectorNamespace,
+        group: ObjectId,
+    ) -> anyhow::Result<()> {
+        vectors::delete_group(&self.records, namespace, group).await
+    }
+}
@@ -0,0 +1,159 @@
+pub mod atlas;
+pub mod brute_force;
+
+use std::{env, sync::Arc};
+
+use anyhow::bail;
+use model::vector::{VectorNamespace, VectorRecord};
+use mongodb::{
+    Client, IndexModel,
+    bson::{Document, doc, oid::ObjectId},
+};
+
+use crate::data::mongodb::MongoDataAdapter;
+
+pub const DEFAULT_ATLAS_VECTOR_INDEX: &str = "vector_index";
+
+#[derive(Debug, Clone)]
+pub struct VectorQuery {
+    pub namespace: VectorNamespace,
+    pub vector: Vec<f32>,
+    /// Restricts results to the user's own records.
+    pub user_id: Option<ObjectId>,
+    /// Restricts results to records in these groups. Records of entities shared by other users,
+    /// like an assistant's knowledge, are only reachable this way.
+    pub groups: Option<Vec<ObjectId>>,
+    pub limit: usize,
+    /// Cosine similarity below which records are not returned.
+    pub min_score: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VectorMatch {
+    pub id: ObjectId,
+    pub score: f32,
+}
+
+/// Stores embeddings and finds the most similar ones to a query vector.
+#[async_trait::async_trait]
+pub trait VectorStore: Send + Sync {
+    /// Inserts records, replacing existing ones with the same id.
+    async fn upsert(&self, records: Vec<VectorRecord>) -> anyhow::Result<()>;
+    /// Returns matches ordered by descending similarity.
+    async fn query(&self, query: VectorQuery) -> anyhow::Result<Vec<VectorMatch>>;
+    async fn delete(&self, namespace: VectorNamespace, ids: &[ObjectId]) -> anyhow::Result<()>;
+    async fn delete_group(&self, namespace: VectorNamespace, group: ObjectId)
+    -> anyhow::Result<()>;
+}
+
+/// Builds the store selected by `VECTOR_STORE`: `brute-force` (default) scores every candidate
+/// in process, `atlas` delegates to a MongoDB Atlas vector search index.
+pub async fn from_env(client: Client) -> anyhow::Result<Arc<dyn VectorStore>> {
+    let records = MongoDataAdapter::new(client, "chat".to_string(), "vectors".to_string());
+    migrate(&records).await?;
+
+    match env::var("VECTOR_STORE").as_deref() {
+        Err(_) | Ok("brute-force") => {
+            Ok(Arc::new(brute_force::BruteForceVectorStore::new(records)))
+        }
+        Ok("atlas") => Ok(Arc::new(atlas::AtlasVectorStore::new(
+            records,
+            env::var("ATLAS_VECTOR_INDEX")
+                .unwrap_or_else(|_| DEFAULT_ATLAS_VECTOR_INDEX.to_string()),
+        ))),
+        Ok(other) => bail!("Unknown vector store: {other}"),
+    }
+}
+
+async fn migrate(records: &MongoDataAdapter<VectorRecord>) -> anyhow::Result<()> {
+    records
+        .collection()
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "namespace": 1, "group": 1 })
+                .build(),
+        )
+        .await?;
+    records
+        .collection()
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "namespace": 1, "user_id": 1 })
+                .build(),
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.;
+    }
+
+    let (dot, norm_a, norm_b) = a
+        .iter()
+        .zip(b)
+        .fold((0., 0., 0.), |(dot, norm_a, norm_b), (a, b)| {
+            (dot + a * b, norm_a + a * a, norm_b + b * b)
+        });
+    if norm_a == 0. || norm_b == 0. {
+        return 0.;
+    }
+
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Filter selecting the records a query may return.
+fn filter(query: &VectorQuery) -> Document {
+    let mut filter = doc! { "namespace": namespace(query.namespace) };
+    if let Some(user_id) = query.user_id {
+        filter.insert("user_id", user_id);
+    }
+    if let Some(ref groups) = query.groups {
+        filter.insert("group", doc! { "$in": groups });
+    }
+
+    filter
+}
+
+fn namespace(namespace: VectorNamespace) -> &'static str {
+    match namespace {
+        VectorNamespace::Documents => "documents",
+        VectorNamespace::Memories => "memories",
+        VectorNamespace::Messages => "messages",
+    }
+}
+
+async fn upsert(
+    records: &MongoDataAdapter<VectorRecord>,
+    entries: Vec<VectorRecord>,
+) -> anyhow::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<ObjectId> = entries.iter().map(|record| record.id).collect();
+    records.delete_many(doc! { "_id": { "$in": ids } }).await?;
+    records.create_many(entries).await
+}
+
+async fn delete(
+    records: &MongoDataAdapter<VectorRecord>,
+    namespace_: VectorNamespace,
+    ids: &[ObjectId],
+) -> anyhow::Result<()> {
+    records
+        .delete_many(doc! { "namespace": namespace(namespace_), "_id": { "$in": ids } })
+        .await
+}
+
+async fn delete_group(
+    records: &MongoDataAdapter<VectorRecord>,
+    namespace_: VectorNamespace,
+    group: ObjectId,
+) -> anyhow::Result<()> {
+    records
+        .delete_many(doc! { "namespace": namespace(namespace_), "group": group })
+        .await
+}
@@ -1,20 +1,42 @@
 use ai::openai::completions::OpenAICompletionDelta;
 use model::message::ChatMessage;
+use mongodb::bson::oid::ObjectId;
 use serde::Serialize;
 
-use crate::payload::memories::MemoryPayload;
+use crate::payload::{chat::CitationPayload, memories::MemoryPayload};
 
 pub enum ApiDelta {
     Chunk(OpenAICompletionDelta),
     Control(ControlChunk),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(tag = "kind")]
 pub enum ControlChunk {
-    Done { message: ChatMessage },
+    Done {
+        message: ChatMessage,
+    },
     WebSearchPerformed,
-    ChatNameUpdated { name: String },
-    MemoryAdded { memory: MemoryPayload },
-    InferenceError { code: u16 },
+    ChatNameUpdated {
+        name: String,
+    },
+    MemoryAdded {
+        memory: MemoryPayload,
+    },
+    MemoryUpdated {
+        memory: MemoryPayload,
+    },
+    MemoryRemoved {
+        #[serde(serialize_with = "crate::payload::serialize_oid")]
+        id: ObjectId,
+    },
+    ContextTruncated {
+        dropped_messages: usize,
+    },
+    DocumentsRetrieved {
+        citations: Vec<CitationPayload>,
+    },
+    InferenceError {
+        code: u16,
+    },
 }
@@ -0,0 +1,160 @@
+use std::{env, time::Duration};
+
+use anyhow::Context;
+use chrono::Utc;
+use model::{
+    chat::{Chat, ChatSettings},
+    message::ChatMessage,
+};
+use mongodb::bson::{self, oid::ObjectId};
+use redis_om::redis;
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppState;
+
+const KEY_PREFIX: &str = "temporary_chat:";
+const DEFAULT_TTL_MINUTES: u64 = 60;
+
+/// Chat kept only in Redis, so it leaves no trace once it expires. Nothing about it is written to
+/// MongoDB.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporaryChat {
+    pub id: ObjectId,
+    pub user_id: ObjectId,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+impl TemporaryChat {
+    /// Chat used to build completions. Temporary chats have no name, settings, assistant, project
+    /// or summary.
+    pub fn chat(&self) -> Chat {
+        Chat {
+            id: Some(self.id),
+            name: None,
+            user_id: self.user_id,
+            timestamp: self.timestamp,
+            summary: None,
+            settings: ChatSettings::default(),
+            assistant_id: None,
+            share_id: None,
+            project_id: None,
+            renamed: false,
+            folder_id: None,
+            pinned: false,
+            archived: false,
+            tags: vec![],
+            updated_at: None,
+            deleted_at: None,
+        }
+    }
+}
+
+/// How long a temporary chat is kept after its last message, from `TEMPORARY_CHAT_TTL_MINUTES`.
+/// Read once at startup, see [`AppState::temporary_chat_ttl`].
+pub fn ttl() -> anyhow::Result<Duration> {
+    let minutes = env::var("TEMPORARY_CHAT_TTL_MINUTES")
+        .ok()
+        .map(|minutes| minutes.parse::<u64>())
+        .transpose()
+        .context("Invalid temporary chat TTL")?
+        .unwrap_or(DEFAULT_TTL_MINUTES);
+    if minutes == 0 {
+        anyhow::bail!("Temporary chat TTL must be at least 1 minute");
+    }
+
+    Ok(Duration::from_secs(minutes * 60))
+}
+
+fn key(chat_id: ObjectId) -> String {
+    format!("{KEY_PREFIX}{}", chat_id.to_hex())
+}
+
+fn messages_key(chat_id: ObjectId) -> String {
+    format!("{KEY_PREFIX}{}:messages", chat_id.to_hex())
+}
+
+pub async fn create(state: &AppState, user_id: ObjectId) -> anyhow::Result<TemporaryChat> {
+    let chat = TemporaryChat {
+        id: ObjectId::new(),
+        user_id,
+        timestamp: Utc::now(),
+    };
+
+    let mut connection = state.storage().cache().connection();
+    redis::cmd("SET")
+        .arg(key(chat.id))
+        .arg(bson::to_vec(&chat)?)
+        .arg("EX")
+        .arg(state.temporary_chat_ttl().as_secs())
+        .query_async::<_, ()>(&mut connection)
+        .await?;
+
+    Ok(chat)
+}
+
+/// Loads the user's temporary chat, if it has not expired.
+pub async fn get(
+    state: &AppState,
+    user_id: ObjectId,
+    chat_id: ObjectId,
+) -> anyhow::Result<Option<TemporaryChat>> {
+    let mut connection = state.storage().cache().connection();
+    let data: Option<Vec<u8>> = redis::cmd("GET")
+        .arg(key(chat_id))
+        .query_async(&mut connection)
+        .await?;
+    let Some(data) = data else {
+        return Ok(None);
+    };
+
+    let chat: TemporaryChat = bson::from_slice(&data)?;
+    Ok((chat.user_id == user_id).then_some(chat))
+}
+
+/// Messages of a temporary chat, oldest first.
+pub async fn messages(state: &AppState, chat_id: ObjectId) -> anyhow::Result<Vec<ChatMessage>> {
+    let mut connection = state.storage().cache().connection();
+    let data: Vec<Vec<u8>> = redis::cmd("LRANGE")
+        .arg(messages_key(chat_id))
+        .arg(0)
+        .arg(-1)
+        .query_async(&mut connection)
+        .await?;
+
+    let mut messages = data
+        .iter()
+        .map(|message| bson::from_slice(message))
+        .collect::<Result<Vec<ChatMessage>, _>>()?;
+    // answers are appended when they complete, possibly after the next user message
+    messages.sort_by_key(|message| message.timestamp);
+
+    Ok(messages)
+}
+
+/// Appends a message to its temporary chat and extends the chat's lifetime.
+pub async fn push_message(state: &AppState, message: &ChatMessage) -> anyhow::Result<()> {
+    let ttl = state.temporary_chat_ttl().as_secs() as usize;
+    let mut connection = state.storage().cache().connection();
+    redis::pipe()
+        .atomic()
+        .rpush(messages_key(message.chat_id), bson::to_vec(message)?)
+        .expire(messages_key(message.chat_id), ttl)
+        .expire(key(message.chat_id), ttl)
+        .query_async::<_, ()>(&mut connection)
+        .await?;
+
+    Ok(())
+}
+
+/// Deletes a temporary chat before it expires.
+pub async fn delete(state: &AppState, chat_id: ObjectId) -> anyhow::Result<()> {
+    let mut connection = state.storage().cache().connection();
+    redis::cmd("DEL")
+        .arg(key(chat_id))
+        .arg(messages_key(chat_id))
+        .query_async::<_, ()>(&mut connection)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use std::{env, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use chrono::Utc;
+use futures::TryStreamExt;
+use model::{chat::Chat, vector::VectorNamespace};
+use mongodb::{
+    ClientSession,
+    bson::{self, Document, doc, oid::ObjectId},
+};
+
+use crate::{data::transaction, documents, history, state::AppState};
+
+const DEFAULT_RETENTION_DAYS: u32 = 30;
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Rows removed by a purge.
+#[derive(Debug, Clone, Default)]
+pub struct Purged {
+    pub messages: u64,
+    pub upload_ids: Vec<ObjectId>,
+}
+
+/// How long chats stay in the trash, from `TRASH_RETENTION_DAYS`.
+pub fn retention() -> anyhow::Result<chrono::Duration> {
+    let days = env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .map(|days| days.parse::<u32>())
+        .transpose()
+        .context("Invalid trash retention days")?
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+    Ok(chrono::Duration::days(days.into()))
+}
+
+/// Permanently deletes a chat with its messages, uploads and their files, if it still matches
+/// `condition`, e.g. it was not restored since it was loaded. Returns `None` when it no longer
+/// does. Uploads that were added to a project or an assistant stay with it and are only detached
+/// from the chat.
+///
+/// The rows are deleted in one transaction when the server supports them. A standalone server
+/// does not, so the rows are deleted one collection at a time after the chat, which leaves them
+/// unreachable if a delete fails halfway. Embeddings are not covered by the transaction and are
+/// dropped once the rows are gone.
+pub async fn purge(
+    state: &AppState,
+    chat: &Chat,
+    condition: Document,
+) -> anyhow::Result<Option<Purged>> {
+    let chat_id = chat.id.ok_or_else(|| anyhow::anyhow!("Chat has no id"))?;
+    let upload_ids: Vec<ObjectId> = state
+        .storage()
+        .database()
+        .uploads
+        .collection()
+        .distinct(
+            "_id",
+            doc! {
+                "chat_id": chat_id,
+                "user_id": chat.user_id,
+                "project_id": null,
+                "assistant_id": null,
+            },
+        )
+        .await?
+        .into_iter()
+        .filter_map(|id| id.as_object_id())
+        .collect();
+
+    let purged = match purge_in_transaction(state, chat_id, condition.clone(), &upload_ids).await {
+        Err(e) if transaction::unsupported(&e) => {
+            delete_rows(state, chat_id, condition, &upload_ids, None).await?
+        }
+        result => result?,
+    };
+    let Some(purged) = purged else {
+        return Ok(None);
+    };
+
+    for upload_id in &upload_ids {
+        state
+            .storage()
+            .vectors()
+            .delete_group(VectorNamespace::Documents, *upload_id)
+            .await?;
+    }
+    history::semantic::remove_chat(state, chat_id).await?;
+
+    Ok(Some(purged))
+}
+
+/// Purges the chats that stayed in the trash longer than the retention window, every hour.
+/// Fails when the retention window is misconfigured.
+pub fn spawn_purge(state: Arc<AppState>) -> anyhow::Result<()> {
+    let retention = retention()?;
+    tokio::spawn(async move {
+        loop {
+            match purge_expired(&state, retention).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {purged} chats from the trash."),
+                Err(e) => tracing::error!("Failed to purge the trash: {e}"),
+            }
+            tokio::time::sleep(PURGE_INTERVAL).await;
+        }
+    });
+
+    Ok(())
+}
+
+async fn purge_expired(state: &AppState, retention: chrono::Duration) -> anyhow::Result<usize> {
+    let cutoff = bson::DateTime::from_chrono(Utc::now() - retention);
+    let expired: Vec<Chat> = state
+        .storage()
+        .database()
+        .chats
+        .get_many(doc! { "deleted_at": { "$lte": cutoff } })
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut purged = 0;
+    for chat in expired {
+        match purge(state, &chat, doc! { "deleted_at": { "$lte": cutoff } }).await {
+            Ok(Some(_)) => purged += 1,
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to purge chat {:?}: {e}", chat.id),
+        }
+    }
+
+    Ok(purged)
+}
+
+async fn purge_in_transaction(
+    state: &AppState,
+    chat_id: ObjectId,
+    condition: Document,
+    upload_ids: &[ObjectId],
+) -> mongodb::error::Result<Option<Purged>> {
+    let mut session = state.storage().database().client().start_session().await?;
+    session.start_transaction().await?;
+
+    match delete_rows(state, chat_id, condition, upload_ids, Some(&mut session)).await {
+        Ok(Some(purged)) => {
+            session.commit_transaction().await?;
+            Ok(Some(purged))
+        }
+        Ok(None) => {
+            session.abort_transaction().await?;
+            Ok(None)
+        }
+        Err(e) => {
+            let _ = session.abort_transaction().await;
+            Err(e)
+        }
+    }
+}
+
+/// Deletes the chat if it still matches `condition`, then its rows.
+async fn delete_rows(
+    state: &AppState,
+    chat_id: ObjectId,
+    mut condition: Document,
+    upload_ids: &[ObjectId],
+    mut session: Option<&mut ClientSession>,
+) -> mongodb::error::Result<Option<Purged>> {
+    let database = state.storage().database();
+
+    condition.insert("_id", chat_id);
+    let deleted = transaction::delete_many(
+        &database.chats.collection(),
+        condition,
+        session.as_deref_mut(),
+    )
+    .await?;
+    if deleted == 0 {
+        return Ok(None);
+    }
+
+    documents::delete_rows(state, upload_ids, session.as_deref_mut()).await?;
+    // uploads kept by a project or an assistant
+    transaction::update_many(
+        &database.uploads.collection(),
+        doc! { "chat_id": chat_id },
+        doc! { "$set": { "chat_id": null } },
+        session.as_deref_mut(),
+    )
+    .await?;
+    let messages = transaction::delete_many(
+        &database.messages.collection(),
+        doc! { "chat_id": chat_id },
+        session,
+    )
+    .await?;
+
+    Ok(Some(Purged {
+        messages,
+        upload_ids: upload_ids.to_vec(),
+    }))
+}
@@ -0,0 +1,30 @@
+use bson::oid::ObjectId;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::chat::ChatSettings;
+
+/// Reusable persona that chats can be created from.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Assistant {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: ObjectId,
+    pub name: String,
+    /// Emoji or image URL shown next to the assistant's name.
+    pub avatar: Option<String>,
+    /// System prompt, default model and generation parameters. Chat settings take precedence.
+    pub settings: ChatSettings,
+    pub tools: AssistantTools,
+    /// Uploads attached to every message sent to the assistant.
+    pub knowledge: Vec<ObjectId>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+/// Tools the assistant uses on every message, regardless of the per-message toggles.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct AssistantTools {
+    pub search: bool,
+    pub memories: bool,
+}
@@ -10,4 +10,83 @@ pub struct Chat {
     pub user_id: ObjectId,
     #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub timestamp: chrono::DateTime<Utc>,
+    #[serde(default)]
+    pub summary: Option<ChatSummary>,
+    #[serde(default)]
+    pub settings: ChatSettings,
+    #[serde(default)]
+    pub assistant_id: Option<ObjectId>,
+    /// Share link the chat was created with, when the assistant belongs to someone else. The chat
+    /// keeps access to the assistant only while that link stays active.
+    #[serde(default)]
+    pub share_id: Option<ObjectId>,
+    #[serde(default)]
+    pub project_id: Option<ObjectId>,
+    /// Set once the user names the chat, so automatic renames leave the name alone.
+    #[serde(default)]
+    pub renamed: bool,
+    #[serde(default)]
+    pub folder_id: Option<ObjectId>,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Archived chats are left out of the chat list unless asked for.
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Time of the last message. Chats are listed by it.
+    #[serde(
+        default,
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub updated_at: Option<chrono::DateTime<Utc>>,
+    /// Set while the chat is in the trash. Trashed chats are purged after the retention window.
+    #[serde(
+        default,
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub deleted_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl Chat {
+    /// Time of the chat's last activity. Chats created before activity was tracked fall back to
+    /// their creation time.
+    pub fn updated_at(&self) -> chrono::DateTime<Utc> {
+        self.updated_at.unwrap_or(self.timestamp)
+    }
+}
+
+/// Per-chat overrides applied to every completion in the chat.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ChatSettings {
+    pub system_prompt: Option<String>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub stop: Vec<String>,
+    pub seed: Option<i64>,
+    /// Model used when a message does not specify one.
+    pub default_model: Option<String>,
+    pub default_reasoning_effort: Option<ReasoningEffort>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+/// Running summary of the oldest messages in a chat, used in place of history
+/// that no longer fits into the model's context window.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatSummary {
+    pub content: String,
+    /// Timestamp of the newest message covered by the summary.
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub covers_until: chrono::DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: chrono::DateTime<Utc>,
 }
@@ -0,0 +1,16 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// Passage of an uploaded document. Its embedding lives in the vector store under the same id.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DocumentChunk {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub upload_id: ObjectId,
+    pub user_id: ObjectId,
+    /// Position of the chunk within the document.
+    pub index: u32,
+    /// 1-based page the chunk starts on.
+    pub page: Option<u32>,
+    pub content: String,
+}
@@ -0,0 +1,14 @@
+use bson::oid::ObjectId;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Named group the user files chats into.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Folder {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: ObjectId,
+    pub name: String,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub timestamp: chrono::DateTime<Utc>,
+}
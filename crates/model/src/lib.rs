@@ -1,8 +1,14 @@
+pub mod assistant;
 pub mod chat;
+pub mod document;
+pub mod folder;
 pub mod key;
 pub mod memory;
 pub mod message;
+pub mod project;
+pub mod retention;
 pub mod session;
 pub mod share;
 pub mod upload;
 pub mod user;
+pub mod vector;
@@ -1,4 +1,5 @@
 use bson::oid::ObjectId;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -7,4 +8,29 @@ pub struct Memory {
     pub id: Option<ObjectId>,
     pub user_id: ObjectId,
     pub content: String,
+    /// Set when the memory only applies to chats of a project with scoped memories.
+    #[serde(default)]
+    pub project_id: Option<ObjectId>,
+    /// Whether the memory's embedding is in the vector store.
+    #[serde(default)]
+    pub indexed: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Pinned memories are included in every prompt, regardless of relevance.
+    #[serde(default)]
+    pub pinned: bool,
+    /// User message the memory was extracted from. Unset for memories added by the user.
+    #[serde(default)]
+    pub source_message_id: Option<ObjectId>,
+    /// Unset for memories created before timestamps were recorded.
+    #[serde(
+        default,
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub created_at: Option<chrono::DateTime<Utc>>,
+    #[serde(
+        default,
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub updated_at: Option<chrono::DateTime<Utc>>,
 }
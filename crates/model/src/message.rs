@@ -17,11 +17,27 @@ pub struct ChatMessage {
     pub updated_memory: Option<String>,
     #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub timestamp: chrono::DateTime<Utc>,
+    /// Document passages that were retrieved into the context for this reply.
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    /// Set once the message is embedded for semantic search.
+    #[serde(default)]
+    pub indexed: bool,
 }
 
-impl Into<Bson> for ChatMessageContent {
-    fn into(self) -> Bson {
-        match self {
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Citation {
+    pub upload_id: ObjectId,
+    pub chunk_id: ObjectId,
+    pub page: Option<u32>,
+    pub snippet: String,
+    /// Cosine similarity between the chunk and the user's message.
+    pub score: f32,
+}
+
+impl From<ChatMessageContent> for Bson {
+    fn from(val: ChatMessageContent) -> Self {
+        match val {
             ChatMessageContent::Text { value } => {
                 Bson::Document(doc! { "type": "Text", "value": value })
             }
@@ -46,6 +62,7 @@ pub enum Role {
     #[default]
     User,
     Assistant,
+    System,
 }
 
 impl fmt::Display for Role {
@@ -56,6 +73,7 @@ impl fmt::Display for Role {
             match self {
                 Role::User => "user",
                 Role::Assistant => "assistant",
+                Role::System => "system",
             }
         )
     }
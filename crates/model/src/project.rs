@@ -0,0 +1,18 @@
+use bson::oid::ObjectId;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Group of chats sharing instructions and files.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Project {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: ObjectId,
+    pub name: String,
+    /// Added to the system prompt of every chat in the project.
+    pub instructions: Option<String>,
+    /// Keeps memories created in the project's chats apart from the user's other memories.
+    pub scoped_memories: bool,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub timestamp: chrono::DateTime<Utc>,
+}
@@ -0,0 +1,25 @@
+use bson::oid::ObjectId;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Record of what a retention sweep purged for a user. Only ids and counts are kept, never
+/// content.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionAudit {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: ObjectId,
+    /// Retention period in effect for the user, in days.
+    pub policy_days: u32,
+    /// Data last active before this time was purged.
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub cutoff: chrono::DateTime<Utc>,
+    pub chat_ids: Vec<ObjectId>,
+    /// Messages deleted with the chats or on their own from chats that are still active.
+    pub messages: u64,
+    /// Uploads deleted with the chats and messages or on their own.
+    pub upload_ids: Vec<ObjectId>,
+    pub memory_ids: Vec<ObjectId>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub timestamp: chrono::DateTime<Utc>,
+}
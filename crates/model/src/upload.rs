@@ -9,4 +9,21 @@ pub struct UserUpload {
     pub user_id: ObjectId,
     pub content_type: String,
     pub is_sent: bool,
+    /// Set when the upload is pinned as knowledge of an assistant.
+    #[serde(default)]
+    pub assistant_id: Option<ObjectId>,
+    /// Set when the upload belongs to the file set of a project.
+    #[serde(default)]
+    pub project_id: Option<ObjectId>,
+    /// Progress of text extraction and embedding. Unset for files that are not indexed.
+    #[serde(default)]
+    pub ingestion: Option<IngestionStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IngestionStatus {
+    Pending,
+    Indexed,
+    Failed,
 }
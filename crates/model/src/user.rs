@@ -7,4 +7,24 @@ pub struct User {
     pub id: Option<ObjectId>,
     pub email: String,
     pub password: String,
+    #[serde(default)]
+    pub settings: UserSettings,
+}
+
+/// Preferences applied to every chat of the user.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct UserSettings {
+    /// What the user wants the assistant to know about them.
+    pub about_me: Option<String>,
+    /// How the user wants the assistant to respond.
+    pub response_instructions: Option<String>,
+    /// Model used when neither the message nor the chat specify one.
+    pub default_model: Option<String>,
+    /// IANA time zone name, e.g. `Europe/Berlin`.
+    pub timezone: Option<String>,
+    /// BCP 47 language tag, e.g. `en-US`.
+    pub locale: Option<String>,
+    /// Days after their last activity that chats and memories are purged. The deployment's
+    /// `RETENTION_DAYS` applies when it is shorter.
+    pub retention_days: Option<u32>,
 }
@@ -0,0 +1,23 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// Embedding of an indexed entity, stored next to the entity's id.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VectorRecord {
+    /// Id of the embedded entity, e.g. a document chunk.
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub namespace: VectorNamespace,
+    pub user_id: ObjectId,
+    /// Entity the record is grouped under, e.g. the upload a chunk belongs to.
+    pub group: Option<ObjectId>,
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorNamespace {
+    Documents,
+    Memories,
+    Messages,
+}
@@ -3,9 +3,14 @@ import type { Chat } from "../model/chat";
 import type { Share } from "../model/share";
 import type { ListWindow } from "../util";
 
-export async function chats(window: ListWindow): Promise<Chat[]> {
+export interface ChatList {
+  chats: Chat[];
+  next_cursor: string | null;
+}
+
+export async function chats(window: ListWindow): Promise<ChatList> {
   const response = await fetch(
-    `${BACKEND_URI}/chats?start=${Math.floor(window.start)}&take=${Math.floor(window.take)}`,
+    `${BACKEND_URI}/chats?limit=${Math.floor(window.limit)}${window.cursor ? `&cursor=${window.cursor}` : ""}`,
     {
       credentials: "include",
     },
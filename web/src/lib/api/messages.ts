@@ -9,13 +9,21 @@ import type { ListWindow } from "../util";
 import { is, subscribeToStream } from "./completions";
 import type { ApiError } from "./error";
 
+export interface ChatMessages {
+  /** Newest first. */
+  messages: ChatMessage[];
+  /** Cursor of the older messages, null when the first message is loaded. */
+  before: string | null;
+  after: string | null;
+}
+
 export async function fetchChatMessages(
   chatId: string,
   window: ListWindow,
   share_id?: string
-): Promise<ChatMessage[] | ApiError> {
+): Promise<ChatMessages | ApiError> {
   const response = await fetch(
-    `${BACKEND_URI}/chats/${chatId}/messages?start=${Math.floor(window.start)}&take=${Math.floor(window.take)}${share_id ? `&share_id=${share_id}` : ""}`,
+    `${BACKEND_URI}/chats/${chatId}/messages?limit=${Math.floor(window.limit)}${window.cursor ? `&before=${window.cursor}` : ""}${share_id ? `&share_id=${share_id}` : ""}`,
     {
       credentials: "include",
     },
@@ -16,6 +16,8 @@ export interface ChatStore {
       chat: Chat;
       state: ChatState;
       messages: ChatMessage[];
+      /** Cursor of the messages older than the loaded ones, null when all are loaded. */
+      before: string | null;
       streaming: boolean;
       searching: boolean;
     }
@@ -49,6 +51,7 @@ export interface ChatStore {
     delta: { content: string | null; reasoning: string | null, memory: string | null },
   ) => void;
   setChatMessages: (id: string, messages: ChatMessage[]) => void;
+  setChatCursor: (id: string, before: string | null) => void;
   setChatState: (id: string, state: ChatState) => void;
   clearPendingMessage: (id: string) => void;
   updatePendingMessageMemory: (id: string, memory: string) => void;
@@ -109,6 +112,7 @@ export const useChatsStore = create<ChatStore>((set, get) => ({
           chat,
           state: { status: messages ? "success" : "idle" },
           messages: messages ?? [],
+          before: null,
           streaming: false,
           searching: false,
         },
@@ -137,6 +141,14 @@ export const useChatsStore = create<ChatStore>((set, get) => ({
       };
     });
   },
+  setChatCursor: (id, before) => {
+    set((state) => ({
+      chats: {
+        ...state.chats,
+        [id]: { ...state.chats[id], before },
+      },
+    }));
+  },
   prependChatMessages: (id, messages) => {
     set((state) => ({
       chats: {
@@ -45,10 +45,10 @@ export async function init() {
   useAuthStore.subscribe((store, prev) => {
     if (store.user && !prev.user) {
       chats({
-        start: 0,
-        take: Math.round((window.innerHeight * 1.5) / 50),
-      }).then((chats) => {
-        for (const chat of chats) {
+        limit: Math.round((window.innerHeight * 1.5) / 50),
+      }).then(({ chats }) => {
+        // chats are added in front of each other, so the newest one is added last
+        for (const chat of [...chats].reverse()) {
           // listUnsentFiles(chat.id).then(uploads => {
           //   setUploads(chat.id, uploads);
           // });
@@ -1,4 +1,5 @@
 export interface ListWindow {
-  start: number;
-  take: number;
+  /** Opaque cursor returned with the previous page. */
+  cursor?: string | null;
+  limit: number;
 }
@@ -37,6 +37,7 @@ export function Chat() {
   const chatsLoaded = useChatsStore((state) => !state.isFetching);
   const setMessages = useChatsStore((state) => state.setChatMessages);
   const prependMessages = useChatsStore((state) => state.prependChatMessages);
+  const setCursor = useChatsStore((state) => state.setChatCursor);
   const setChatState = useChatsStore((state) => state.setChatState);
   const pendingMessage = useChatsStore(
     (state) => state.pendingMessages[chatId],
@@ -44,7 +45,6 @@ export function Chat() {
   const isSearching = useChatsStore((state) => state.chats[chatId]?.searching);
   // const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
-  const isLastBatch = useRef(false);
   const [batchLoadError, setBatchLoadError] = useState<string | null>(null);
   const [batchLoading, setBatchLoading] = useState(false);
   const batchLoadingInternal = useRef(false);
@@ -89,23 +89,22 @@ export function Chat() {
     if (!scrollWrapper.current || !messages) return;
     const wrapper = scrollWrapper.current;
     const handler = () => {
-      if (isLastBatch.current || batchLoading || batchLoadingInternal.current)
+      if (!chat.before || batchLoading || batchLoadingInternal.current)
         return;
       if (wrapper.scrollTop < 100) {
         setBatchLoading(true);
         batchLoadingInternal.current = true;
         fetchChatMessages(chat.chat.id, {
-          start: messages.length,
-          take: window.innerHeight / 100,
+          cursor: chat.before,
+          limit: window.innerHeight / 100,
         }).then((batch) => {
           if (isError(batch)) {
             setBatchLoadError(batch.error);
             return;
           }
 
-          if (batch.length < Math.floor(window.innerHeight / 100))
-            isLastBatch.current = true;
-          prependMessages(chat.chat.id, batch);
+          setCursor(chat.chat.id, batch.before);
+          prependMessages(chat.chat.id, batch.messages);
           setBatchLoading(false);
           batchLoadingInternal.current = false;
         });
@@ -119,8 +118,8 @@ export function Chat() {
     messages,
     chat,
     prependMessages,
+    setCursor,
     batchLoading,
-    isLastBatch,
   ]);
 
   useEffect(() => {
@@ -148,8 +147,7 @@ export function Chat() {
       // setIsLoading(true);
       setChatState(chatId, { status: "loading" });
       fetchChatMessages(chatId, {
-        start: 0,
-        take: window.innerHeight / 100,
+        limit: window.innerHeight / 100,
       }).then((result) => {
         if (isError(result)) {
           setChatState(chatId, { status: "error", error: result.error });
@@ -157,7 +155,8 @@ export function Chat() {
           return;
         }
         setChatState(chatId, { status: "success" });
-        setMessages(chatId, result);
+        setCursor(chatId, result.before);
+        setMessages(chatId, result.messages);
         scrollWrapper.current?.scrollTo({
           top: scrollableContainer.current?.clientHeight,
           behavior: "instant",
@@ -167,7 +166,7 @@ export function Chat() {
     } else {
       // setIsLoading(false);
     }
-  }, [messages, chatId, setMessages, setChatState, chatLoading, chatExists]);
+  }, [messages, chatId, setMessages, setCursor, setChatState, chatLoading, chatExists]);
 
   return (
     <div
@@ -22,7 +22,7 @@ export function SharedChat() {
   const [chatExists, setChatExists] = useState(true);
   const [chat, setChat] = useState<Chat | null>(null);
   const [error, setError] = useState<string | null>(null);
-  const isLastBatch = useRef(false);
+  const before = useRef<string | null>(null);
   const [batchLoadError, setBatchLoadError] = useState<string | null>(null);
   const [batchLoading, setBatchLoading] = useState(false);
   const batchLoadingInternal = useRef(false);
@@ -62,23 +62,22 @@ export function SharedChat() {
     if (!scrollWrapper.current || !messages || !chat || !shareId) return;
     const wrapper = scrollWrapper.current;
     const handler = () => {
-      if (isLastBatch.current || batchLoading || batchLoadingInternal.current)
+      if (!before.current || batchLoading || batchLoadingInternal.current)
         return;
       if (wrapper.scrollTop < 100) {
         setBatchLoading(true);
         batchLoadingInternal.current = true;
         fetchChatMessages(chat.id, {
-          start: messages.length,
-          take: window.innerHeight / 100,
+          cursor: before.current,
+          limit: window.innerHeight / 100,
         }, shareId).then((batch) => {
           if (isError(batch)) {
             setBatchLoadError(batch.error);
             return;
           }
 
-          if (batch.length < Math.floor(window.innerHeight / 100))
-            isLastBatch.current = true;
-          setMessages(prev => [...prev, ...batch]);
+          before.current = batch.before;
+          setMessages(prev => [...prev, ...batch.messages]);
           setBatchLoading(false);
           batchLoadingInternal.current = false;
         });
@@ -92,7 +91,7 @@ export function SharedChat() {
     messages,
     chat,
     batchLoading,
-    isLastBatch,
+    before,
   ]);
 
   useEffect(() => {
@@ -112,14 +111,14 @@ export function SharedChat() {
     if (!chat || !shareId) return;
 
     fetchChatMessages(chat.id, {
-      start: 0,
-      take: window.innerHeight / 100,
+      limit: window.innerHeight / 100,
     }, shareId).then((result) => {
       if (isError(result)) {
         setError(result.error);
         return;
       }
-      setMessages(result);
+      before.current = result.before;
+      setMessages(result.messages);
       scrollWrapper.current?.scrollTo({
         top: scrollableContainer.current?.clientHeight,
         behavior: "instant",